@@ -28,30 +28,48 @@ async fn main() {
             }
         }
         Err(err) => match err {
-            Error::NotFoundError => {
+            Error::NotFoundError(_) => {
                 println!("Not Found: {}", err);
             }
-            Error::AuthenticationError => {
+            Error::AuthenticationError(_) => {
                 println!("Authentication Issue: {}", err);
             }
-            Error::RateLimitError => {
+            Error::RateLimitError(_) => {
                 println!("Rate Limit: {}", err);
             }
-            Error::InvalidRequestError => {
+            Error::InvalidRequestError(_) => {
                 println!("Invalid Request: {}", err);
             }
-            Error::ListenApiError => {
+            Error::ListenApiError(_) => {
                 println!("API Error: {}", err);
             }
-            Error::ApiConnectionError => {
+            Error::BadGatewayError(_) | Error::ServiceUnavailableError(_) | Error::GatewayTimeoutError(_) => {
+                println!("API Unavailable: {}", err);
+            }
+            Error::ApiConnectionError { .. } => {
                 println!("Connection Issue: {}", err);
             }
+            Error::UnexpectedStatus(context) => {
+                println!("Unexpected Status: {}", context.status);
+            }
+            Error::ValidationError(message) => {
+                println!("Invalid Parameters: {}", message);
+            }
+            Error::CassetteError(message) => {
+                println!("Cassette Error: {}", message);
+            }
             Error::Reqwest(err) => {
                 println!("Reqwest HTTP Client Error: {}", err);
             }
             Error::Json(err) => {
                 println!("JSON Parsing Error: {}", err);
             }
+            Error::Io(err) => {
+                println!("IO Error: {}", err);
+            }
+            _ => {
+                println!("Error: {}", err);
+            }
         },
     };
 }
@@ -1,12 +1,15 @@
+use std::sync::Arc;
+
 /// API url and key context.
-pub enum Api<'a> {
+#[derive(Clone)]
+pub enum Api {
     /// API context for Listen Notes production API.
-    Production(&'a str),
+    Production(Arc<str>),
     /// API context for Listen Notes mock API for testing.
     Mock,
 }
 
-impl Api<'_> {
+impl Api {
     pub fn url(&self) -> &str {
         match &self {
             Api::Production(_) => "https://listen-api.listennotes.com/api/v2",
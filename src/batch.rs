@@ -0,0 +1,101 @@
+//! Fetching any number of podcasts or episodes with the batch endpoints, which accept a limited number of ids per call.
+use super::Result;
+use futures::{StreamExt, TryStreamExt};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+
+/// Maximum number of ids of a single call to `POST /podcasts` or `POST /episodes`.
+pub(crate) const MAX_BATCH_IDS: usize = 10;
+
+/// Results of [`Client::fetch_podcasts_by_ids`](super::Client::fetch_podcasts_by_ids) and
+/// [`Client::fetch_episodes_by_ids`](super::Client::fetch_episodes_by_ids).
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchResults<T> {
+    /// Fetched items, in the order of their ids, without duplicates.
+    pub found: Vec<T>,
+    /// Ids for which the API returned nothing, in their order.
+    pub not_found: Vec<String>,
+}
+
+/// Fetches `ids` in chunks of [`MAX_BATCH_IDS`] with `fetch`, running up to `concurrency` calls at once.
+pub(crate) async fn fetch_by_ids<'a, T, F, Fut>(
+    ids: &[&'a str],
+    concurrency: usize,
+    fetch: F,
+    id: fn(&T) -> &str,
+) -> Result<BatchResults<T>>
+where
+    F: Fn(Vec<&'a str>) -> Fut,
+    Fut: Future<Output = Result<Vec<T>>>,
+{
+    let mut seen = HashSet::new();
+    let ids: Vec<&'a str> = ids.iter().copied().filter(|id| seen.insert(*id)).collect();
+
+    let pages: Vec<Vec<T>> = futures::stream::iter(ids.chunks(MAX_BATCH_IDS).map(|chunk| fetch(chunk.to_vec())))
+        .buffered(concurrency.max(1))
+        .try_collect()
+        .await?;
+    let mut items: HashMap<String, T> = HashMap::new();
+    for item in pages.into_iter().flatten() {
+        items.entry(id(&item).to_owned()).or_insert(item);
+    }
+
+    let mut results = BatchResults {
+        found: Vec::with_capacity(ids.len()),
+        not_found: Vec::new(),
+    };
+    for id in ids {
+        match items.remove(id) {
+            Some(item) => results.found.push(item),
+            None => results.not_found.push(id.to_owned()),
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn chunks_in_order() {
+        let ids: Vec<String> = (0..25).map(|i| i.to_string()).collect();
+        let mut ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+        ids.push("3");
+        ids.push("missing");
+        let calls = Mutex::new(Vec::new());
+
+        let results = futures::executor::block_on(fetch_by_ids(
+            &ids,
+            2,
+            |chunk| {
+                calls.lock().unwrap().push(chunk.len());
+                let found: Vec<String> = chunk
+                    .into_iter()
+                    .filter(|id| *id != "missing")
+                    .rev()
+                    .map(str::to_owned)
+                    .collect();
+                async move { Ok(found) }
+            },
+            String::as_str,
+        ))
+        .unwrap();
+
+        assert_eq!(*calls.lock().unwrap(), vec![10, 10, 6]);
+        assert_eq!(results.found, ids[..25].to_vec());
+        assert_eq!(results.not_found, vec!["missing".to_owned()]);
+    }
+
+    #[test]
+    fn fails_with_chunk() {
+        let results = futures::executor::block_on(fetch_by_ids(
+            &["a", "b"],
+            1,
+            |_| async { Err::<Vec<String>, _>(crate::Error::ValidationError("ids".to_owned())) },
+            String::as_str,
+        ));
+        assert!(matches!(results, Err(crate::Error::ValidationError(_))));
+    }
+}
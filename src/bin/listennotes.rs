@@ -0,0 +1,434 @@
+//! `listennotes`, a command-line client for exploring the Listen API, built with the `cli` feature.
+//!
+//! ```sh
+//! LISTEN_API_KEY=... listennotes search "star wars" --type podcast -o table
+//! ```
+use clap::{Parser, Subcommand, ValueEnum};
+use podcast_api::{Client, Error};
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Explore the Listen Notes Podcast API from the command line.
+///
+/// Without an api key, the mock API is called, which returns fake data.
+#[derive(Debug, Parser)]
+#[command(name = "listennotes", version)]
+struct Cli {
+    /// API key, defaults to $LISTEN_API_KEY or the `api_key` of the config file.
+    #[arg(long, global = true, env = "LISTEN_API_KEY", hide_env_values = true)]
+    api_key: Option<String>,
+    /// Config file with an `api_key = ...` line [default: ~/.config/listennotes/config].
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// Base URL of API calls, e.g. http://localhost:8080/api/v2.
+    #[arg(long, global = true)]
+    base_url: Option<String>,
+    /// Output format.
+    #[arg(long, short, global = true, value_enum, default_value_t = Output::Json)]
+    output: Output,
+    /// Extra parameter of the endpoint, e.g. `-p safe_mode=1`.
+    #[arg(long = "param", short = 'p', global = true, value_name = "KEY=VALUE", value_parser = parse_param)]
+    params: Vec<(String, String)>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Output {
+    /// Pretty-printed JSON response.
+    Json,
+    /// Fields and lists of the response as aligned columns.
+    Table,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Full-text search of episodes, podcasts or curated lists (GET /search).
+    Search {
+        /// Search term.
+        q: String,
+        /// What to search: episode, podcast or curated.
+        #[arg(long = "type")]
+        kind: Option<String>,
+        /// Offset for pagination, from `next_offset` of the previous page.
+        #[arg(long)]
+        offset: Option<u64>,
+    },
+    /// Autocomplete search terms, and optionally genres and podcasts (GET /typeahead).
+    Typeahead {
+        /// Partial search term.
+        q: String,
+        /// Also suggest podcasts.
+        #[arg(long)]
+        show_podcasts: bool,
+        /// Also suggest genres.
+        #[arg(long)]
+        show_genres: bool,
+    },
+    /// Podcast with its episodes (GET /podcasts/{id}).
+    Podcast {
+        /// Podcast id.
+        id: String,
+        /// Episode order: recent_first or oldest_first.
+        #[arg(long)]
+        sort: Option<String>,
+        /// Episodes before or after this time, from `next_episode_pub_date` of the previous page.
+        #[arg(long)]
+        next_episode_pub_date: Option<u64>,
+    },
+    /// Episode (GET /episodes/{id}).
+    Episode {
+        /// Episode id.
+        id: String,
+    },
+    /// Best podcasts, optionally by genre and region (GET /best_podcasts).
+    Best {
+        /// Genre id, see `genres`.
+        #[arg(long)]
+        genre_id: Option<u64>,
+        /// Region code, e.g. us.
+        #[arg(long)]
+        region: Option<String>,
+        /// Page number, starting at 1.
+        #[arg(long)]
+        page: Option<u64>,
+    },
+    /// Podcast genres (GET /genres).
+    Genres {
+        /// Only top level genres.
+        #[arg(long)]
+        top_level_only: bool,
+    },
+    /// Playlists of your account (GET /playlists).
+    Playlists {
+        /// Page number, starting at 1.
+        #[arg(long)]
+        page: Option<u64>,
+    },
+    /// Submits a podcast by its RSS url (POST /podcasts/submit).
+    Submit {
+        /// RSS url.
+        rss: String,
+        /// Email address to notify once the podcast is added.
+        #[arg(long)]
+        email: Option<String>,
+    },
+    /// Requests deletion of a podcast (DELETE /podcasts/{id}).
+    Delete {
+        /// Podcast id.
+        id: String,
+        /// Why the podcast should be deleted.
+        #[arg(long)]
+        reason: Option<String>,
+    },
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli).await {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            ExitCode::from(exit_code(&err))
+        }
+    }
+}
+
+async fn run(cli: Cli) -> podcast_api::Result<String> {
+    let api_key = match cli.api_key {
+        Some(api_key) => Some(api_key),
+        None => config_api_key(cli.config)?,
+    };
+    let mut client = Client::builder();
+    if let Some(api_key) = api_key {
+        client = client.api_key(api_key);
+    }
+    if let Some(base_url) = cli.base_url {
+        client = client.base_url(base_url);
+    }
+    let client = client.build()?;
+
+    let mut parameters = Map::new();
+    let mut set = |key: &str, value: Option<Value>| {
+        if let Some(value) = value {
+            parameters.insert(key.to_owned(), value);
+        }
+    };
+    let response = match &cli.command {
+        Command::Search { q, kind, offset } => {
+            set("q", Some(q.as_str().into()));
+            set("type", kind.as_deref().map(Value::from));
+            set("offset", offset.map(Value::from));
+            client.search(&with_params(parameters, &cli.params)).await?
+        }
+        Command::Typeahead {
+            q,
+            show_podcasts,
+            show_genres,
+        } => {
+            set("q", Some(q.as_str().into()));
+            set("show_podcasts", flag(*show_podcasts));
+            set("show_genres", flag(*show_genres));
+            client.typeahead(&with_params(parameters, &cli.params)).await?
+        }
+        Command::Podcast {
+            id,
+            sort,
+            next_episode_pub_date,
+        } => {
+            set("sort", sort.as_deref().map(Value::from));
+            set("next_episode_pub_date", next_episode_pub_date.map(Value::from));
+            client
+                .fetch_podcast_by_id(id, &with_params(parameters, &cli.params))
+                .await?
+        }
+        Command::Episode { id } => {
+            client
+                .fetch_episode_by_id(id, &with_params(parameters, &cli.params))
+                .await?
+        }
+        Command::Best { genre_id, region, page } => {
+            set("genre_id", genre_id.map(Value::from));
+            set("region", region.as_deref().map(Value::from));
+            set("page", page.map(Value::from));
+            client
+                .fetch_best_podcasts(&with_params(parameters, &cli.params))
+                .await?
+        }
+        Command::Genres { top_level_only } => {
+            set("top_level_only", flag(*top_level_only));
+            client
+                .fetch_podcast_genres(&with_params(parameters, &cli.params))
+                .await?
+        }
+        Command::Playlists { page } => {
+            set("page", page.map(Value::from));
+            client.fetch_my_playlists(&with_params(parameters, &cli.params)).await?
+        }
+        Command::Submit { rss, email } => {
+            set("rss", Some(rss.as_str().into()));
+            set("email", email.as_deref().map(Value::from));
+            client.submit_podcast(&with_params(parameters, &cli.params)).await?
+        }
+        Command::Delete { id, reason } => {
+            set("reason", reason.as_deref().map(Value::from));
+            client.delete_podcast(id, &with_params(parameters, &cli.params)).await?
+        }
+    };
+
+    let body = response.json().await?;
+    Ok(match cli.output {
+        Output::Json => serde_json::to_string_pretty(&body)?,
+        Output::Table => table(&body),
+    })
+}
+
+/// Exit code of a failed command; 2 is used by clap for invalid arguments.
+fn exit_code(err: &Error) -> u8 {
+    match err {
+        Error::ValidationError(_) => 2,
+        Error::AuthenticationError(_) => 3,
+        Error::NotFoundError(_) => 4,
+        Error::InvalidRequestError(_) => 5,
+        Error::RateLimitError(_) => 6,
+        Error::ApiConnectionError { .. } => 7,
+        Error::ListenApiError(_)
+        | Error::BadGatewayError(_)
+        | Error::ServiceUnavailableError(_)
+        | Error::GatewayTimeoutError(_)
+        | Error::UnexpectedStatus(_) => 8,
+        _ => 1,
+    }
+}
+
+/// Listen API flags are `0` or `1`, and left out when not set.
+fn flag(value: bool) -> Option<Value> {
+    if value {
+        Some(1.into())
+    } else {
+        None
+    }
+}
+
+fn with_params(mut parameters: Map<String, Value>, params: &[(String, String)]) -> Value {
+    for (key, value) in params {
+        parameters.insert(key.to_owned(), value.as_str().into());
+    }
+    Value::Object(parameters)
+}
+
+fn parse_param(param: &str) -> Result<(String, String), String> {
+    match param.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_owned(), value.to_owned())),
+        _ => Err(format!("expected KEY=VALUE, got `{}`", param)),
+    }
+}
+
+/// Api key from the config file at `path`, or at the default location if it exists.
+fn config_api_key(path: Option<PathBuf>) -> podcast_api::Result<Option<String>> {
+    let contents = match path {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => match default_config().and_then(|path| std::fs::read_to_string(path).ok()) {
+            Some(contents) => contents,
+            None => return Ok(None),
+        },
+    };
+    Ok(parse_config(&contents))
+}
+
+fn default_config() -> Option<PathBuf> {
+    let config = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(config) => PathBuf::from(config),
+        None => PathBuf::from(std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?).join(".config"),
+    };
+    Some(config.join("listennotes").join("config"))
+}
+
+/// Value of the `api_key = ...` line, ignoring `#` comments and quotes.
+fn parse_config(contents: &str) -> Option<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "api_key")
+        .map(|(_, value)| value.trim().trim_matches('"').to_owned())
+        .filter(|api_key| !api_key.is_empty())
+}
+
+/// Scalar fields of `body`, followed by its lists with the id, title and publisher or parent of each item.
+fn table(body: &Value) -> String {
+    let object = match body.as_object() {
+        Some(object) => object,
+        None => return scalar(body),
+    };
+
+    let mut fields = Vec::new();
+    let mut lists = Vec::new();
+    for (key, value) in object {
+        match value {
+            Value::Array(items) => lists.push((key, items)),
+            Value::Object(_) => {}
+            value => fields.push(vec![key.to_owned(), scalar(value)]),
+        }
+    }
+
+    let mut sections = Vec::new();
+    if !fields.is_empty() {
+        sections.push(columns(&fields));
+    }
+    for (key, items) in lists {
+        let rows: Vec<Vec<String>> = items
+            .iter()
+            .map(|item| match item {
+                Value::Object(_) => vec![
+                    scalar(&item["id"]),
+                    scalar(first(item, &["title", "title_original", "name"])),
+                    scalar(first(item, &["publisher", "publisher_original", "parent_id"])),
+                ],
+                item => vec![scalar(item)],
+            })
+            .collect();
+        sections.push(format!("{} ({}):\n{}", key, items.len(), columns(&rows)));
+    }
+    sections.join("\n\n")
+}
+
+/// First of the `keys` of `item` which is not null.
+fn first<'a>(item: &'a Value, keys: &[&str]) -> &'a Value {
+    keys.iter()
+        .map(|key| &item[*key])
+        .find(|value| !value.is_null())
+        .unwrap_or(&Value::Null)
+}
+
+fn scalar(value: &Value) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.to_owned(),
+        value => value.to_string(),
+    };
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() > 60 {
+        format!("{}...", text.chars().take(57).collect::<String>())
+    } else {
+        text
+    }
+}
+
+fn columns(rows: &[Vec<String>]) -> String {
+    let mut widths = Vec::new();
+    for row in rows {
+        for (index, cell) in row.iter().enumerate() {
+            let width = cell.chars().count();
+            match widths.get_mut(index) {
+                Some(max) if *max < width => *max = width,
+                Some(_) => {}
+                None => widths.push(width),
+            }
+        }
+    }
+    rows.iter()
+        .map(|row| {
+            let line: Vec<String> = row
+                .iter()
+                .enumerate()
+                .map(|(index, cell)| format!("{:width$}", cell, width = widths[index]))
+                .collect();
+            line.join("  ").trim_end().to_owned()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn exit_codes() {
+        let connection = || Error::ApiConnectionError {
+            method: http::Method::GET,
+            endpoint: "/api/v2/search".to_owned(),
+            source: None,
+            attempts: 3,
+        };
+        assert_eq!(exit_code(&connection()), 7);
+        assert_eq!(exit_code(&Error::ValidationError("q".to_owned())), 2);
+        assert_eq!(exit_code(&Error::CassetteError("no recording".to_owned())), 1);
+    }
+
+    #[test]
+    fn config() {
+        assert_eq!(
+            parse_config("# listennotes\napi_key = \"abc\"\n"),
+            Some("abc".to_owned())
+        );
+        assert_eq!(parse_config("#api_key = abc\nother = 1"), None);
+        assert_eq!(parse_param("safe_mode=1"), Ok(("safe_mode".to_owned(), "1".to_owned())));
+        assert!(parse_param("=1").is_err());
+    }
+
+    #[test]
+    fn table_output() {
+        let body = json!({
+            "total": 2,
+            "next_offset": 10,
+            "results": [
+                { "id": "a", "title_original": "Startup", "publisher_original": "Gimlet" },
+                { "id": "b", "title_original": "Star   Talk\n", "publisher_original": null }
+            ],
+            "terms": ["star wars"]
+        });
+        assert_eq!(
+            table(&body),
+            "next_offset  10\ntotal        2\n\nresults (2):\na  Startup    Gimlet\nb  Star Talk\n\nterms (1):\nstar wars"
+        );
+    }
+}
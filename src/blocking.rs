@@ -0,0 +1,574 @@
+//! Blocking client for code that does not run on an async runtime.
+//!
+//! Enabled with the `blocking` feature. [`Client`] has the same endpoints as the async
+//! [`Client`](crate::Client), but each call blocks the current thread until the response arrives:
+//! ```no_run
+//! use serde_json::json;
+//!
+//! let client = podcast_api::blocking::Client::new(Some("YOUR-API-KEY"));
+//! let results = client.search(&json!({ "q": "star wars" })).unwrap().search_results().unwrap();
+//! println!("{} results", results.total);
+//! ```
+//! Blocking clients must not be used from within an async runtime. Cassettes, response caches and
+//! result streams are only available on the async client.
+
+use super::client::{retry_after, DEFAULT_USER_AGENT};
+use super::form;
+use super::{model, Api, Error, ErrorContext, RateLimiter, Result, RetryPolicy, Usage};
+use http::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::blocking::RequestBuilder;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Blocking client for accessing Listen Notes API.
+///
+/// Cloning is cheap: clones share the HTTP connection pool and plan usage.
+#[derive(Clone)]
+pub struct Client {
+    /// HTTP client.
+    client: reqwest::blocking::Client,
+    /// API context.
+    api: Api,
+    /// Base URL of API calls, defaults to the url of `api`.
+    base_url: Arc<str>,
+    /// User Agent Header for API calls.
+    user_agent: Arc<str>,
+    /// Policy for retrying failed API calls.
+    retry_policy: RetryPolicy,
+    /// Limiter throttling API calls.
+    rate_limiter: Option<RateLimiter>,
+    /// Latest plan usage reported by the API.
+    usage: Arc<Mutex<Usage>>,
+}
+
+#[derive(Debug)]
+/// Response and request context for blocking API call.
+pub struct Response {
+    /// HTTP response.
+    pub response: reqwest::blocking::Response,
+    /// HTTP request that resulted in this response.
+    pub request: reqwest::blocking::Request,
+    /// Number of attempts it took to get this response, including retries.
+    pub attempts: u32,
+}
+
+impl Response {
+    /// Get plan usage from the response headers.
+    pub fn usage(&self) -> Usage {
+        Usage::from_headers(self.response.headers())
+    }
+
+    /// Get JSON data object from [`reqwest::blocking::Response`].
+    pub fn json(self) -> Result<Value> {
+        self.response.json().map_err(Error::from)
+    }
+
+    /// Deserialize JSON data into any type, e.g. [`model::SearchResults<model::SearchPodcast>`] for a podcast search.
+    pub fn parse<T: DeserializeOwned>(self) -> Result<T> {
+        self.response.json().map_err(Error::from)
+    }
+
+    /// Get typed response of [`Client::search`] and [`Client::search_episode_titles`].
+    pub fn search_results(self) -> Result<model::SearchResults> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::typeahead`].
+    pub fn typeahead_results(self) -> Result<model::TypeaheadResults> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::spellcheck`].
+    pub fn spellcheck(self) -> Result<model::Spellcheck> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::fetch_related_searches`] and [`Client::fetch_trending_searches`].
+    pub fn search_terms(self) -> Result<model::SearchTerms> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::fetch_best_podcasts`].
+    pub fn best_podcasts(self) -> Result<model::BestPodcasts> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::fetch_podcast_by_id`].
+    pub fn podcast(self) -> Result<model::Podcast> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::batch_fetch_podcasts`].
+    pub fn batch_podcasts(self) -> Result<model::BatchPodcasts> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::fetch_episode_by_id`].
+    pub fn episode(self) -> Result<model::Episode<model::Podcast>> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::batch_fetch_episodes`].
+    pub fn batch_episodes(self) -> Result<model::BatchEpisodes> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::fetch_curated_podcasts_list_by_id`].
+    pub fn curated_list(self) -> Result<model::CuratedList<model::Podcast>> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::fetch_curated_podcasts_lists`].
+    pub fn curated_lists(self) -> Result<model::CuratedLists> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::fetch_podcast_genres`].
+    pub fn genres(self) -> Result<model::Genres> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::fetch_podcast_regions`].
+    pub fn regions(self) -> Result<model::Regions> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::fetch_podcast_languages`].
+    pub fn languages(self) -> Result<model::Languages> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::just_listen`].
+    pub fn random_episode(self) -> Result<model::Episode> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::fetch_recommendations_for_podcast`].
+    pub fn podcast_recommendations(self) -> Result<model::PodcastRecommendations> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::fetch_recommendations_for_episode`].
+    pub fn episode_recommendations(self) -> Result<model::EpisodeRecommendations> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::fetch_playlist_by_id`].
+    pub fn playlist(self) -> Result<model::Playlist> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::fetch_my_playlists`].
+    pub fn playlists(self) -> Result<model::Playlists> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::submit_podcast`].
+    pub fn podcast_submission(self) -> Result<model::PodcastSubmission> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::delete_podcast`].
+    pub fn podcast_deletion(self) -> Result<model::PodcastDeletion> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::fetch_audience_for_podcast`].
+    pub fn audience(self) -> Result<model::Audience> {
+        self.parse()
+    }
+
+    /// Get typed response of [`Client::fetch_podcasts_by_domain`].
+    pub fn domain_podcasts(self) -> Result<model::DomainPodcasts> {
+        self.parse()
+    }
+}
+
+/// Builder for a blocking [`Client`] with custom base URL, timeouts, proxy and headers.
+///
+/// Created with [`Client::builder`]. Has the settings of the async [`ClientBuilder`](crate::ClientBuilder)
+/// that the blocking client supports.
+#[derive(Debug, Default)]
+pub struct ClientBuilder {
+    builder: crate::ClientBuilder,
+}
+
+impl ClientBuilder {
+    /// Creates builder for a mock API client with 30 second timeouts.
+    pub fn new() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
+    /// API key for the production API; without one the client calls the mock API.
+    pub fn api_key(self, api_key: impl Into<String>) -> Self {
+        self.map(|builder| builder.api_key(api_key))
+    }
+
+    /// Base URL of API calls, e.g. `http://localhost:8080/api/v2`, instead of the production or mock API.
+    pub fn base_url(self, base_url: impl Into<String>) -> Self {
+        self.map(|builder| builder.base_url(base_url))
+    }
+
+    /// Timeout of each attempt of an API call, from connecting until the response body is read.
+    ///
+    /// `None` disables the timeout.
+    pub fn timeout(self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.map(|builder| builder.timeout(timeout))
+    }
+
+    /// Timeout for connecting to the API servers.
+    pub fn connect_timeout(self, connect_timeout: Duration) -> Self {
+        self.map(|builder| builder.connect_timeout(connect_timeout))
+    }
+
+    /// Proxy for all API calls.
+    pub fn proxy(self, proxy: reqwest::Proxy) -> Self {
+        self.map(|builder| builder.proxy(proxy))
+    }
+
+    /// Extra header sent with every API call, replacing any earlier value of the same header.
+    pub fn header(self, name: HeaderName, value: HeaderValue) -> Self {
+        self.map(|builder| builder.header(name, value))
+    }
+
+    /// Extra headers sent with every API call.
+    pub fn headers(self, headers: HeaderMap) -> Self {
+        self.map(|builder| builder.headers(headers))
+    }
+
+    /// User Agent Header for API calls.
+    pub fn user_agent(self, user_agent: impl Into<String>) -> Self {
+        self.map(|builder| builder.user_agent(user_agent))
+    }
+
+    /// Retries failed API calls according to `retry_policy`, see [`Client::with_retry_policy`].
+    pub fn retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        self.map(|builder| builder.retry_policy(retry_policy))
+    }
+
+    /// Throttles API calls with `rate_limiter`, see [`Client::with_rate_limiter`].
+    pub fn rate_limiter(self, rate_limiter: RateLimiter) -> Self {
+        self.map(|builder| builder.rate_limiter(rate_limiter))
+    }
+
+    /// Builds the client.
+    ///
+    /// Fails with [`Error::ValidationError`] for an invalid base URL, or with [`Error::Reqwest`]
+    /// if the HTTP client cannot be created, e.g. because TLS is unavailable.
+    pub fn build(self) -> Result<Client> {
+        self.builder.build_blocking()
+    }
+
+    fn map(self, f: impl FnOnce(crate::ClientBuilder) -> crate::ClientBuilder) -> Self {
+        ClientBuilder {
+            builder: f(self.builder),
+        }
+    }
+}
+
+impl Client {
+    /// Creates new blocking Listen API Client.
+    ///
+    /// Uses default HTTP client with 30 second timeouts. Without an api key, the client calls the
+    /// mock API.
+    /// ```
+    /// let client = podcast_api::blocking::Client::new(None);
+    /// ```
+    pub fn new(id: Option<&str>) -> Client {
+        let mut builder = Client::builder();
+        if let Some(id) = id {
+            builder = builder.api_key(id);
+        }
+        builder.build().expect("blocking::Client::new()")
+    }
+
+    /// Creates new blocking Listen API Client with user provided HTTP Client.
+    pub fn new_custom(client: reqwest::blocking::Client, id: Option<&str>, user_agent: Option<&str>) -> Client {
+        Client::from_parts(
+            client,
+            if let Some(id) = id {
+                Api::Production(id.into())
+            } else {
+                Api::Mock
+            },
+            None,
+            user_agent.unwrap_or(DEFAULT_USER_AGENT),
+        )
+    }
+
+    /// Creates builder for configuring base URL, timeouts, proxy and headers of a new client.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let client = podcast_api::blocking::Client::builder()
+    ///     .api_key("YOUR-API-KEY")
+    ///     .timeout(Duration::from_secs(10))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    pub(crate) fn from_parts(
+        client: reqwest::blocking::Client,
+        api: Api,
+        base_url: Option<&str>,
+        user_agent: &str,
+    ) -> Client {
+        Client {
+            client,
+            base_url: base_url.unwrap_or_else(|| api.url()).trim_end_matches('/').into(),
+            api,
+            user_agent: user_agent.into(),
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None,
+            usage: Arc::new(Mutex::new(Usage::default())),
+        }
+    }
+
+    /// Retries failed API calls according to `retry_policy`, sleeping on the current thread in between.
+    ///
+    /// By default, failed API calls are not retried.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Throttles API calls, including retries, with `rate_limiter`, blocking the current thread until
+    /// each call may be made.
+    ///
+    /// By default, API calls are not throttled.
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Base URL of API calls, e.g. `https://listen-api.listennotes.com/api/v2`.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Latest plan usage reported by the API across all calls made with this client and its clones.
+    pub fn usage(&self) -> Usage {
+        self.usage.lock().expect("usage lock poisoned").clone()
+    }
+
+    /// Calls [`GET /search`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-search) with supplied parameters.
+    pub fn search(&self, parameters: &Value) -> Result<Response> {
+        self.get("search", parameters)
+    }
+
+    /// Calls [`GET /search_episode_titles`](https://www.listennotes.com/api/docs/#get-api-v2-search_episode_titles) with supplied parameters.
+    pub fn search_episode_titles(&self, parameters: &Value) -> Result<Response> {
+        self.get("search_episode_titles", parameters)
+    }
+
+    /// Calls [`GET /typeahead`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-typeahead) with supplied parameters.
+    pub fn typeahead(&self, parameters: &Value) -> Result<Response> {
+        self.get("typeahead", parameters)
+    }
+
+    /// Calls [`GET /spellcheck`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-spellcheck) with supplied parameters.
+    pub fn spellcheck(&self, parameters: &Value) -> Result<Response> {
+        self.get("spellcheck", parameters)
+    }
+
+    /// Calls [`GET /related_searches`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-related_searches) with supplied parameters.
+    pub fn fetch_related_searches(&self, parameters: &Value) -> Result<Response> {
+        self.get("related_searches", parameters)
+    }
+
+    /// Calls [`GET /trending_searches`](https://www.listennotes.com/api/docs/#get-api-v2-trending_searches) with supplied parameters.
+    pub fn fetch_trending_searches(&self, parameters: &Value) -> Result<Response> {
+        self.get("trending_searches", parameters)
+    }
+
+    /// Calls [`GET /best_podcasts`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-best_podcasts) with supplied parameters.
+    pub fn fetch_best_podcasts(&self, parameters: &Value) -> Result<Response> {
+        self.get("best_podcasts", parameters)
+    }
+
+    /// Calls [`GET /podcasts/{id}`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-podcasts-id) with supplied parameters.
+    pub fn fetch_podcast_by_id(&self, id: &str, parameters: &Value) -> Result<Response> {
+        self.get(&format!("podcasts/{}", id), parameters)
+    }
+
+    /// Calls [`POST /podcasts`](https://www.listennotes.com/podcast-api/docs/#post-api-v2-podcasts) with supplied parameters.
+    pub fn batch_fetch_podcasts(&self, parameters: &Value) -> Result<Response> {
+        self.post("podcasts", parameters)
+    }
+
+    /// Calls [`GET /episodes/{id}`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-episodes-id) with supplied parameters.
+    pub fn fetch_episode_by_id(&self, id: &str, parameters: &Value) -> Result<Response> {
+        self.get(&format!("episodes/{}", id), parameters)
+    }
+
+    /// Calls [`POST /episodes`](https://www.listennotes.com/podcast-api/docs/#post-api-v2-episodes) with supplied parameters.
+    pub fn batch_fetch_episodes(&self, parameters: &Value) -> Result<Response> {
+        self.post("episodes", parameters)
+    }
+
+    /// Calls [`GET /curated_podcasts/{id}`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-curated_podcasts-id) with supplied parameters.
+    pub fn fetch_curated_podcasts_list_by_id(&self, id: &str, parameters: &Value) -> Result<Response> {
+        self.get(&format!("curated_podcasts/{}", id), parameters)
+    }
+
+    /// Calls [`GET /curated_podcasts`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-curated_podcasts) with supplied parameters.
+    pub fn fetch_curated_podcasts_lists(&self, parameters: &Value) -> Result<Response> {
+        self.get("curated_podcasts", parameters)
+    }
+
+    /// Calls [`GET /genres`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-genres) with supplied parameters.
+    pub fn fetch_podcast_genres(&self, parameters: &Value) -> Result<Response> {
+        self.get("genres", parameters)
+    }
+
+    /// Calls [`GET /regions`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-regions) with supplied parameters.
+    pub fn fetch_podcast_regions(&self, parameters: &Value) -> Result<Response> {
+        self.get("regions", parameters)
+    }
+
+    /// Calls [`GET /languages`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-languages) with supplied parameters.
+    pub fn fetch_podcast_languages(&self, parameters: &Value) -> Result<Response> {
+        self.get("languages", parameters)
+    }
+
+    /// Calls [`GET /just_listen`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-just_listen) with supplied parameters.
+    pub fn just_listen(&self, parameters: &Value) -> Result<Response> {
+        self.get("just_listen", parameters)
+    }
+
+    /// Calls [`GET /podcasts/{id}/recommendations`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-podcasts-id-recommendations) with supplied parameters.
+    pub fn fetch_recommendations_for_podcast(&self, id: &str, parameters: &Value) -> Result<Response> {
+        self.get(&format!("podcasts/{}/recommendations", id), parameters)
+    }
+
+    /// Calls [`GET /episodes/{id}/recommendations`](https://www.listennotes.com/api/docs/#get-api-v2-episodes-id-recommendations) with supplied parameters.
+    pub fn fetch_recommendations_for_episode(&self, id: &str, parameters: &Value) -> Result<Response> {
+        self.get(&format!("episodes/{}/recommendations", id), parameters)
+    }
+
+    /// Calls [`GET /playlists/{id}`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-playlists-id) with supplied parameters.
+    pub fn fetch_playlist_by_id(&self, id: &str, parameters: &Value) -> Result<Response> {
+        self.get(&format!("playlists/{}", id), parameters)
+    }
+
+    /// Calls [`GET /playlists`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-playlists) with supplied parameters.
+    pub fn fetch_my_playlists(&self, parameters: &Value) -> Result<Response> {
+        self.get("playlists", parameters)
+    }
+
+    /// Calls [`POST /podcasts/submit`](https://www.listennotes.com/podcast-api/docs/#post-api-v2-podcasts-submit) with supplied parameters.
+    pub fn submit_podcast(&self, parameters: &Value) -> Result<Response> {
+        self.post("podcasts/submit", parameters)
+    }
+
+    /// Calls [`DELETE /podcasts/{id}`](https://www.listennotes.com/podcast-api/docs/#delete-api-v2-podcasts-id) with supplied parameters.
+    pub fn delete_podcast(&self, id: &str, parameters: &Value) -> Result<Response> {
+        self.delete(&format!("podcasts/{}", id), parameters)
+    }
+
+    /// Calls [`GET /podcasts/{id}/audience`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-podcasts-id-audience) with supplied parameters.
+    pub fn fetch_audience_for_podcast(&self, id: &str, parameters: &Value) -> Result<Response> {
+        self.get(&format!("podcasts/{}/audience", id), parameters)
+    }
+
+    /// Calls [`GET /podcasts/domains/{domain_name}`](https://www.listennotes.com/api/docs/#get-api-v2-podcasts-domains-domain_name) with supplied parameters.
+    pub fn fetch_podcasts_by_domain(&self, domain_name: &str, parameters: &Value) -> Result<Response> {
+        self.get(&format!("podcasts/domains/{}", domain_name), parameters)
+    }
+
+    fn get(&self, endpoint: &str, parameters: &Value) -> Result<Response> {
+        let request = self
+            .client
+            .get(format!("{}/{}", self.base_url, endpoint))
+            .query(&form::pairs(parameters));
+
+        self.request(request)
+    }
+
+    fn post(&self, endpoint: &str, parameters: &Value) -> Result<Response> {
+        let request = self
+            .client
+            .post(format!("{}/{}", self.base_url, endpoint))
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(form::encode(parameters));
+
+        self.request(request)
+    }
+
+    fn delete(&self, endpoint: &str, parameters: &Value) -> Result<Response> {
+        let request = self
+            .client
+            .delete(format!("{}/{}", self.base_url, endpoint))
+            .query(&form::pairs(parameters));
+
+        self.request(request)
+    }
+
+    fn request(&self, request: RequestBuilder) -> Result<Response> {
+        let request = if let Api::Production(key) = &self.api {
+            request.header("X-ListenAPI-Key", key.as_ref())
+        } else {
+            request
+        }
+        .header("User-Agent", self.user_agent.as_ref())
+        .build()?;
+
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire_blocking();
+            }
+            let (response, retry_after) = self.execute(&request);
+            match response {
+                Ok(response) => {
+                    return Ok(Response {
+                        response,
+                        request,
+                        attempts,
+                    })
+                }
+                Err(err) if self.retry_policy.should_retry(attempts, &err) => {
+                    std::thread::sleep(self.retry_policy.delay(attempts, retry_after));
+                }
+                Err(err) => return Err(err.with_attempts(attempts)),
+            }
+        }
+    }
+
+    /// Executes a single attempt of `request`, along with the delay requested by a `Retry-After` header.
+    fn execute(&self, request: &reqwest::blocking::Request) -> (Result<reqwest::blocking::Response>, Option<Duration>) {
+        let request = request.try_clone().expect(
+            "Error can remain unhandled because we're not using streams, which are the try_clone fail condition",
+        );
+        let (method, url) = (request.method().clone(), request.url().clone());
+        let response = self
+            .client
+            .execute(request)
+            .map_err(|err| Error::from_send(&method, &url, err));
+
+        if let Ok(response) = &response {
+            self.usage
+                .lock()
+                .expect("usage lock poisoned")
+                .update(Usage::from_headers(response.headers()));
+        }
+        let retry_after = response
+            .as_ref()
+            .ok()
+            .and_then(|response| retry_after(response.headers()));
+        let response = match response {
+            Ok(response) if response.status().is_success() => Ok(response),
+            Ok(response) => Err(Error::from_context(ErrorContext::from_blocking_response(response))),
+            Err(err) => Err(err),
+        };
+
+        (response, retry_after)
+    }
+}
@@ -0,0 +1,275 @@
+use super::client::DEFAULT_USER_AGENT;
+use super::{Api, Client, Error, RateLimiter, Result, RetryPolicy};
+#[cfg(not(target_arch = "wasm32"))]
+use super::{Cassette, ResponseCache};
+use http::header::{HeaderMap, HeaderName, HeaderValue};
+use std::time::Duration;
+
+/// Builder for a [`Client`] with custom base URL, timeouts, proxy and headers.
+///
+/// Created with [`Client::builder`].
+#[derive(Debug)]
+pub struct ClientBuilder {
+    api_key: Option<String>,
+    base_url: Option<String>,
+    timeout: Option<Duration>,
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    connect_timeout: Option<Duration>,
+    #[cfg(not(target_arch = "wasm32"))]
+    proxy: Option<reqwest::Proxy>,
+    headers: HeaderMap,
+    user_agent: Option<String>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    #[cfg(not(target_arch = "wasm32"))]
+    cassette: Option<Cassette>,
+    #[cfg(not(target_arch = "wasm32"))]
+    cache: Option<ResponseCache>,
+}
+
+impl Default for ClientBuilder {
+    fn default() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+}
+
+impl ClientBuilder {
+    /// Creates builder for a mock API client with 30 second timeouts.
+    pub fn new() -> ClientBuilder {
+        ClientBuilder {
+            api_key: None,
+            base_url: None,
+            timeout: Some(Duration::from_secs(30)),
+            connect_timeout: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            proxy: None,
+            headers: HeaderMap::new(),
+            user_agent: None,
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            cassette: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            cache: None,
+        }
+    }
+
+    /// API key for the production API; without one the client calls the mock API.
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Base URL of API calls, e.g. `http://localhost:8080/api/v2`, instead of the production or mock API.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Timeout of each attempt of an API call, from connecting until the response body is read.
+    ///
+    /// `None` disables the timeout.
+    pub fn timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.timeout = timeout.into();
+        self
+    }
+
+    /// Timeout for connecting to the API servers.
+    ///
+    /// Ignored on wasm, where the browser or runtime manages connections.
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Proxy for all API calls.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Extra header sent with every API call, replacing any earlier value of the same header.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Extra headers sent with every API call.
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    /// User Agent Header for API calls.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Retries failed API calls according to `retry_policy`, see [`Client::with_retry_policy`].
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Throttles API calls with `rate_limiter`, see [`Client::with_rate_limiter`].
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Records API calls to `cassette`, or replays them from it, see [`Client::with_cassette`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Caches responses of rarely changing endpoints in `cache`, see [`Client::with_cache`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Builds the client.
+    ///
+    /// Fails with [`Error::ValidationError`] for an invalid base URL, or with [`Error::Reqwest`]
+    /// if the HTTP client cannot be created, e.g. because TLS is unavailable.
+    pub fn build(self) -> Result<Client> {
+        self.validate_base_url()?;
+
+        #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+        let mut client = reqwest::ClientBuilder::new().default_headers(self.headers);
+        // The wasm HTTP client has neither timeouts nor proxies, the client enforces timeouts itself.
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if let Some(timeout) = self.timeout {
+                client = client.timeout(timeout);
+            }
+            if let Some(connect_timeout) = self.connect_timeout {
+                client = client.connect_timeout(connect_timeout);
+            }
+            if let Some(proxy) = self.proxy {
+                client = client.proxy(proxy);
+            }
+        }
+
+        let client = Client::from_parts(
+            client.build()?,
+            match self.api_key {
+                Some(api_key) => Api::Production(api_key.into()),
+                None => Api::Mock,
+            },
+            self.base_url.as_deref(),
+            self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT),
+        )
+        .with_retry_policy(self.retry_policy);
+        let client = match self.rate_limiter {
+            Some(rate_limiter) => client.with_rate_limiter(rate_limiter),
+            None => client,
+        };
+        #[cfg(target_arch = "wasm32")]
+        let client = client.with_timeout(self.timeout);
+        #[cfg(not(target_arch = "wasm32"))]
+        let client = match self.cassette {
+            Some(cassette) => client.with_cassette(cassette),
+            None => client,
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        let client = match self.cache {
+            Some(cache) => client.with_cache(cache),
+            None => client,
+        };
+        Ok(client)
+    }
+
+    /// Builds a [`blocking::Client`](crate::blocking::Client).
+    ///
+    /// Fails like [`ClientBuilder::build`], and with [`Error::ValidationError`] if a cassette or
+    /// cache is set, since the blocking client supports neither.
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(self) -> Result<crate::blocking::Client> {
+        self.validate_base_url()?;
+        if self.cassette.is_some() || self.cache.is_some() {
+            return Err(Error::ValidationError(
+                "cassettes and caches are not supported by the blocking client".to_owned(),
+            ));
+        }
+
+        let mut client = reqwest::blocking::ClientBuilder::new()
+            .default_headers(self.headers)
+            .timeout(self.timeout);
+        if let Some(connect_timeout) = self.connect_timeout {
+            client = client.connect_timeout(connect_timeout);
+        }
+        if let Some(proxy) = self.proxy {
+            client = client.proxy(proxy);
+        }
+
+        let client = crate::blocking::Client::from_parts(
+            client.build()?,
+            match self.api_key {
+                Some(api_key) => Api::Production(api_key.into()),
+                None => Api::Mock,
+            },
+            self.base_url.as_deref(),
+            self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT),
+        )
+        .with_retry_policy(self.retry_policy);
+        Ok(match self.rate_limiter {
+            Some(rate_limiter) => client.with_rate_limiter(rate_limiter),
+            None => client,
+        })
+    }
+
+    fn validate_base_url(&self) -> Result<()> {
+        if let Some(base_url) = &self.base_url {
+            match reqwest::Url::parse(base_url) {
+                Ok(url) if !url.cannot_be_a_base() && matches!(url.scheme(), "http" | "https") => {}
+                _ => return Err(Error::ValidationError(format!("invalid base url: {}", base_url))),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_url() {
+        let client = ClientBuilder::new()
+            .base_url("http://localhost:8080/api/v2/")
+            .build()
+            .unwrap();
+        assert_eq!(client.base_url(), "http://localhost:8080/api/v2");
+        assert_eq!(
+            ClientBuilder::new().api_key("key").build().unwrap().base_url(),
+            "https://listen-api.listennotes.com/api/v2"
+        );
+        assert!(matches!(
+            ClientBuilder::new().base_url("localhost:8080").build(),
+            Err(Error::ValidationError(_))
+        ));
+        assert!(matches!(
+            ClientBuilder::new().base_url("not a url").build(),
+            Err(Error::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn proxy_and_headers() {
+        let client = ClientBuilder::new()
+            .proxy(reqwest::Proxy::all("http://localhost:3128").unwrap())
+            .header(HeaderName::from_static("x-team"), HeaderValue::from_static("podcasts"))
+            .connect_timeout(Duration::from_secs(1))
+            .timeout(None)
+            .user_agent("my-app")
+            .build();
+        assert!(client.is_ok());
+    }
+}
@@ -0,0 +1,348 @@
+use super::client::{header_pairs, response_from_parts};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// Storage of cached responses for [`ResponseCache`].
+///
+/// Implement this trait to keep responses somewhere else than in memory, e.g. in Redis.
+pub trait CacheStore: Send + Sync {
+    /// Gets response stored for `key`, expired or not.
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+    /// Stores `response` for `key`, replacing any earlier one.
+    fn insert(&self, key: &str, response: CachedResponse);
+    /// Removes response stored for `key`, if any.
+    fn remove(&self, key: &str);
+}
+
+/// Response stored in a [`CacheStore`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// HTTP headers.
+    pub headers: Vec<(String, String)>,
+    /// Response body.
+    pub body: Vec<u8>,
+    /// Time after which the response must not be used anymore.
+    pub expires_at: SystemTime,
+}
+
+/// In-memory [`CacheStore`] evicting the least recently used response when full.
+#[derive(Debug)]
+pub struct MemoryStore {
+    capacity: usize,
+    entries: Mutex<Entries>,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    /// Response and last use of each key.
+    responses: HashMap<String, (u64, CachedResponse)>,
+    /// Keys by last use.
+    uses: BTreeMap<u64, String>,
+    clock: u64,
+}
+
+impl MemoryStore {
+    /// Creates store for up to `capacity` responses.
+    pub fn new(capacity: usize) -> MemoryStore {
+        MemoryStore {
+            capacity: capacity.max(1),
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    /// Number of stored responses.
+    pub fn len(&self) -> usize {
+        self.entries().responses.len()
+    }
+
+    /// Whether no responses are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries.lock().expect("cache lock poisoned")
+    }
+}
+
+impl Entries {
+    /// Marks `key` as used now.
+    fn touch(&mut self, key: &str) {
+        self.clock += 1;
+        let clock = self.clock;
+        if let Some((used, _)) = self.responses.get_mut(key) {
+            self.uses.remove(used);
+            *used = clock;
+            self.uses.insert(clock, key.to_owned());
+        }
+    }
+}
+
+impl CacheStore for MemoryStore {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut entries = self.entries();
+        entries.touch(key);
+        entries.responses.get(key).map(|(_, response)| response.clone())
+    }
+
+    fn insert(&self, key: &str, response: CachedResponse) {
+        let mut entries = self.entries();
+        if let Some((used, _)) = entries.responses.insert(key.to_owned(), (0, response)) {
+            entries.uses.remove(&used);
+        }
+        entries.touch(key);
+        while entries.responses.len() > self.capacity {
+            let (_, oldest) = entries.uses.pop_first().expect("cache entries in use order");
+            entries.responses.remove(&oldest);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let mut entries = self.entries();
+        if let Some((used, _)) = entries.responses.remove(key) {
+            entries.uses.remove(&used);
+        }
+    }
+}
+
+/// Cache for responses of rarely changing endpoints, to save quota.
+///
+/// Only successful `GET` calls to endpoints with a time-to-live are cached. By default these are
+/// `genres`, `regions` and `languages` for a day, and `curated_podcasts/*` for an hour.
+/// ```
+/// use std::time::Duration;
+///
+/// let client = podcast_api::Client::new(None).with_cache(
+///     podcast_api::ResponseCache::new()
+///         .ttl("podcasts/*", Duration::from_secs(600))
+///         .ttl("curated_podcasts/*", None),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct ResponseCache {
+    store: Arc<dyn CacheStore>,
+    ttls: Vec<(String, Duration)>,
+}
+
+impl std::fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ResponseCache").field("ttls", &self.ttls).finish()
+    }
+}
+
+impl Default for ResponseCache {
+    fn default() -> ResponseCache {
+        ResponseCache::new()
+    }
+}
+
+impl ResponseCache {
+    /// Creates cache keeping up to 1000 responses in memory, with default time-to-live of endpoints.
+    pub fn new() -> ResponseCache {
+        ResponseCache::with_store(MemoryStore::new(1000))
+    }
+
+    /// Creates cache keeping responses in `store`, with default time-to-live of endpoints.
+    pub fn with_store(store: impl CacheStore + 'static) -> ResponseCache {
+        ResponseCache {
+            store: Arc::new(store),
+            ttls: Vec::new(),
+        }
+        .ttl("genres", Duration::from_secs(24 * 60 * 60))
+        .ttl("regions", Duration::from_secs(24 * 60 * 60))
+        .ttl("languages", Duration::from_secs(24 * 60 * 60))
+        .ttl("curated_podcasts/*", Duration::from_secs(60 * 60))
+    }
+
+    /// Time-to-live of responses of `endpoint`, e.g. `genres` or `podcasts/*`, where `*` matches any
+    /// single path segment.
+    ///
+    /// `None` stops caching the endpoint.
+    pub fn ttl(mut self, endpoint: &str, ttl: impl Into<Option<Duration>>) -> Self {
+        let endpoint = endpoint.trim_matches('/');
+        self.ttls.retain(|(pattern, _)| pattern != endpoint);
+        if let Some(ttl) = ttl.into() {
+            self.ttls.push((endpoint.to_owned(), ttl));
+        }
+        self
+    }
+
+    /// Time-to-live of responses of `endpoint`, if they are cached.
+    pub(crate) fn ttl_for(&self, endpoint: &str) -> Option<Duration> {
+        self.ttls
+            .iter()
+            .find(|(pattern, _)| {
+                pattern.split('/').count() == endpoint.split('/').count()
+                    && pattern
+                        .split('/')
+                        .zip(endpoint.split('/'))
+                        .all(|(pattern, segment)| pattern == "*" || pattern == segment)
+            })
+            .map(|(_, ttl)| *ttl)
+    }
+
+    /// Key of `request`, independent of the order of its parameters.
+    ///
+    /// Includes a hash of the api key, so clients with different keys sharing a store don't get
+    /// each other's responses, without writing the key itself to the store.
+    pub(crate) fn key(request: &reqwest::Request) -> String {
+        let mut url = request.url().clone();
+        let mut query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        query.sort();
+        url.set_query(None);
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+        match request.headers().get("x-listenapi-key") {
+            Some(api_key) => format!("{} {} {:016x}", request.method(), url, fnv1a(api_key.as_bytes())),
+            None => format!("{} {}", request.method(), url),
+        }
+    }
+
+    /// Gets unexpired response for `key`.
+    pub(crate) fn get(&self, key: &str) -> Option<CachedResponse> {
+        let response = self.store.get(key)?;
+        if response.expires_at > SystemTime::now() {
+            Some(response)
+        } else {
+            self.store.remove(key);
+            None
+        }
+    }
+
+    /// Stores successful `response` for `key`, and returns it again.
+    ///
+    /// Plan usage and request id headers are only returned, not stored, since they describe this one call
+    /// and would be stale on cache hits.
+    pub(crate) async fn insert(
+        &self,
+        key: &str,
+        ttl: Duration,
+        response: reqwest::Response,
+    ) -> crate::Result<reqwest::Response> {
+        let url = response.url().clone();
+        let mut cached = CachedResponse {
+            status: response.status().as_u16(),
+            headers: header_pairs(response.headers()),
+            body: response.bytes().await?.to_vec(),
+            expires_at: SystemTime::now() + ttl,
+        };
+        let response = cached.to_response(&url);
+        cached.headers.retain(|(name, _)| !is_per_call_header(name));
+        self.store.insert(key, cached);
+        Ok(response)
+    }
+}
+
+/// Whether header `name` describes a single API call, like `X-ListenAPI-Usage` or `X-Request-ID`.
+fn is_per_call_header(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.starts_with("x-listenapi-") || name == "x-request-id"
+}
+
+/// 64-bit FNV-1a hash, stable across Rust versions and platforms unlike `DefaultHasher`.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+impl CachedResponse {
+    pub(crate) fn to_response(&self, url: &reqwest::Url) -> reqwest::Response {
+        response_from_parts(url, self.status, &self.headers, self.body.clone())
+            .unwrap_or_else(|_| http::Response::new(self.body.clone()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(body: &str, ttl: Duration) -> CachedResponse {
+        CachedResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: body.as_bytes().to_vec(),
+            expires_at: SystemTime::now() + ttl,
+        }
+    }
+
+    #[test]
+    fn least_recently_used() {
+        let store = MemoryStore::new(2);
+        store.insert("a", response("a", Duration::from_secs(60)));
+        store.insert("b", response("b", Duration::from_secs(60)));
+        assert!(store.get("a").is_some());
+        store.insert("c", response("c", Duration::from_secs(60)));
+        assert_eq!(store.len(), 2);
+        assert!(store.get("a").is_some());
+        assert!(store.get("b").is_none());
+        assert!(store.get("c").is_some());
+        store.remove("a");
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn ttl() {
+        let cache = ResponseCache::new()
+            .ttl("podcasts/*", Duration::from_secs(60))
+            .ttl("/genres/", None);
+        assert_eq!(cache.ttl_for("podcasts/abc"), Some(Duration::from_secs(60)));
+        assert_eq!(cache.ttl_for("podcasts/abc/audience"), None);
+        assert_eq!(cache.ttl_for("curated_podcasts/abc"), Some(Duration::from_secs(3600)));
+        assert_eq!(cache.ttl_for("genres"), None);
+        assert_eq!(cache.ttl_for("search"), None);
+    }
+
+    #[test]
+    fn expiry() {
+        let cache = ResponseCache::new();
+        cache.store.insert("fresh", response("{}", Duration::from_secs(60)));
+        cache.store.insert(
+            "stale",
+            CachedResponse {
+                expires_at: SystemTime::now() - Duration::from_secs(1),
+                ..response("{}", Duration::from_secs(0))
+            },
+        );
+        assert!(cache.get("fresh").is_some());
+        assert!(cache.get("stale").is_none());
+        assert!(cache.store.get("stale").is_none());
+    }
+
+    #[test]
+    fn key() {
+        let client = reqwest::Client::new();
+        let request = |url: &str| client.get(url).build().unwrap();
+        assert_eq!(
+            ResponseCache::key(&request("https://example.com/api/v2/genres?b=2&a=1")),
+            ResponseCache::key(&request("https://example.com/api/v2/genres?a=1&b=2"))
+        );
+        assert_eq!(
+            ResponseCache::key(&request("https://example.com/api/v2/genres")),
+            "GET https://example.com/api/v2/genres"
+        );
+
+        let with_key = |api_key: &str| {
+            ResponseCache::key(
+                &client
+                    .get("https://example.com/api/v2/genres")
+                    .header("X-ListenAPI-Key", api_key)
+                    .build()
+                    .unwrap(),
+            )
+        };
+        assert_ne!(with_key("key1"), with_key("key2"));
+        assert_eq!(with_key("key1"), with_key("key1"));
+        assert!(!with_key("key1").contains("key1"));
+    }
+
+    #[test]
+    fn stable_hash() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
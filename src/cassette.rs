@@ -0,0 +1,262 @@
+use super::client::{header_pairs, response_from_parts};
+use super::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+static REDACTED: &str = "REDACTED";
+
+/// Recording of API calls made through a [`Client`](super::Client), for replaying them offline.
+///
+/// In record mode, every request and its response are kept in memory, with api keys redacted, and written to
+/// a JSON file by [`save`](Cassette::save) or when the cassette is dropped along with its clients.
+/// In replay mode, requests are answered from that file without network access, each recorded
+/// response being used once, in the order it was recorded.
+/// ```no_run
+/// # tokio_test::block_on(async {
+/// use podcast_api::{Cassette, Client};
+/// use serde_json::json;
+///
+/// // Record once against the real API...
+/// let client = Client::new(Some("YOUR-API-KEY")).with_cassette(Cassette::record("tests/cassettes/search.json"));
+/// client.search(&json!({ "q": "startup" })).await.unwrap();
+/// client.cassette().unwrap().save().unwrap();
+///
+/// // ...then replay in tests.
+/// let client = Client::new(None).with_cassette(Cassette::replay("tests/cassettes/search.json").unwrap());
+/// client.search(&json!({ "q": "startup" })).await.unwrap();
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: Mode,
+    interactions: Mutex<Vec<Interaction>>,
+    /// Whether interactions were recorded since the file was last written.
+    unsaved: AtomicBool,
+}
+
+#[derive(Debug)]
+enum Mode {
+    Record,
+    /// Replay, with whether each interaction was used already.
+    Replay(Mutex<Vec<bool>>),
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Tape {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Cassette {
+    /// Creates cassette recording to the file at `path`, replacing any earlier recording.
+    pub fn record(path: impl Into<PathBuf>) -> Cassette {
+        Cassette {
+            path: path.into(),
+            mode: Mode::Record,
+            interactions: Mutex::new(Vec::new()),
+            unsaved: AtomicBool::new(false),
+        }
+    }
+
+    /// Loads cassette from the file at `path` for replaying.
+    pub fn replay(path: impl AsRef<Path>) -> Result<Cassette> {
+        let tape: Tape = serde_json::from_slice(&std::fs::read(path.as_ref())?)?;
+        Ok(Cassette {
+            path: path.as_ref().to_owned(),
+            mode: Mode::Replay(Mutex::new(vec![false; tape.interactions.len()])),
+            interactions: Mutex::new(tape.interactions),
+            unsaved: AtomicBool::new(false),
+        })
+    }
+
+    /// Writes the recorded API calls to the cassette file.
+    ///
+    /// Dropping a recording cassette saves it too, but ignores failures to write the file.
+    pub fn save(&self) -> Result<()> {
+        if !matches!(self.mode, Mode::Record) {
+            return Ok(());
+        }
+        let tape = serde_json::to_vec_pretty(&Tape {
+            interactions: self.interactions().clone(),
+        })?;
+        self.unsaved.store(false, Ordering::SeqCst);
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, tape).inspect_err(|_| self.unsaved.store(true, Ordering::SeqCst))?;
+        Ok(())
+    }
+
+    /// Path of the cassette file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of recorded API calls.
+    pub fn len(&self) -> usize {
+        self.interactions().len()
+    }
+
+    /// Whether no API calls are recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Executes `request`, recording or replaying it.
+    pub(crate) async fn execute(
+        &self,
+        client: &reqwest::Client,
+        request: reqwest::Request,
+    ) -> Result<reqwest::Response> {
+        let recorded = RecordedRequest::from(&request);
+        match &self.mode {
+            Mode::Replay(used) => {
+                let interactions = self.interactions();
+                let mut used = used.lock().expect("cassette lock poisoned");
+                let index = interactions
+                    .iter()
+                    .zip(used.iter())
+                    .position(|(interaction, used)| !used && interaction.request.matches(&recorded))
+                    .ok_or_else(|| {
+                        Error::CassetteError(format!(
+                            "no recorded response for {} {} in {}",
+                            recorded.method,
+                            recorded.url,
+                            self.path.display()
+                        ))
+                    })?;
+                used[index] = true;
+                interactions[index].response.to_response(request.url())
+            }
+            Mode::Record => {
+                let (method, url) = (request.method().clone(), request.url().clone());
+                let response = client
+                    .execute(request)
+                    .await
+                    .map_err(|err| Error::from_send(&method, &url, err))?;
+                let status = response.status().as_u16();
+                let headers = header_pairs(response.headers());
+                let body = String::from_utf8_lossy(&response.bytes().await?).into_owned();
+                let response = RecordedResponse { status, headers, body };
+
+                self.interactions().push(Interaction {
+                    request: recorded,
+                    response: response.clone(),
+                });
+                self.unsaved.store(true, Ordering::SeqCst);
+
+                response.to_response(&url)
+            }
+        }
+    }
+
+    fn interactions(&self) -> std::sync::MutexGuard<'_, Vec<Interaction>> {
+        self.interactions.lock().expect("cassette lock poisoned")
+    }
+}
+
+impl Drop for Cassette {
+    fn drop(&mut self) {
+        if *self.unsaved.get_mut() {
+            self.save().ok();
+        }
+    }
+}
+
+impl From<&reqwest::Request> for RecordedRequest {
+    fn from(request: &reqwest::Request) -> RecordedRequest {
+        RecordedRequest {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            headers: header_pairs(request.headers())
+                .into_iter()
+                .map(|(name, value)| {
+                    if name.eq_ignore_ascii_case("x-listenapi-key") {
+                        (name, REDACTED.to_owned())
+                    } else {
+                        (name, value)
+                    }
+                })
+                .collect(),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(|body| String::from_utf8_lossy(body).into_owned()),
+        }
+    }
+}
+
+impl RecordedRequest {
+    /// Same method, path, query and body, regardless of host, so recordings of the production API
+    /// can be replayed by a client for any API.
+    fn matches(&self, other: &RecordedRequest) -> bool {
+        fn path_and_query(url: &str) -> Option<String> {
+            let url = reqwest::Url::parse(url).ok()?;
+            Some(format!("{}?{}", url.path(), url.query().unwrap_or_default()))
+        }
+        self.method == other.method
+            && path_and_query(&self.url) == path_and_query(&other.url)
+            && self.body == other.body
+    }
+}
+
+impl RecordedResponse {
+    fn to_response(&self, url: &reqwest::Url) -> Result<reqwest::Response> {
+        response_from_parts(url, self.status, &self.headers, self.body.clone())
+            .map_err(|err| Error::CassetteError(format!("invalid recorded response: {}", err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(url: &str, key: &str) -> RecordedRequest {
+        let request = reqwest::Client::new()
+            .get(url)
+            .header("X-ListenAPI-Key", key)
+            .build()
+            .unwrap();
+        RecordedRequest::from(&request)
+    }
+
+    #[test]
+    fn redacts_api_key() {
+        let recorded = request("https://listen-api.listennotes.com/api/v2/search?q=startup", "secret");
+        assert_eq!(
+            recorded.headers,
+            vec![("x-listenapi-key".to_owned(), REDACTED.to_owned())]
+        );
+        assert!(!serde_json::to_string(&recorded).unwrap().contains("secret"));
+    }
+
+    #[test]
+    fn matches_regardless_of_host() {
+        let recorded = request("https://listen-api.listennotes.com/api/v2/search?q=startup", "secret");
+        assert!(recorded.matches(&request("http://127.0.0.1:8080/api/v2/search?q=startup", "other")));
+        assert!(!recorded.matches(&request("http://127.0.0.1:8080/api/v2/search?q=other", "secret")));
+    }
+}
@@ -1,19 +1,50 @@
-use super::{Api, Error, Result};
-use http::StatusCode;
+use super::batch::{fetch_by_ids, BatchResults};
+use super::form;
+use super::params::{BatchEpisodesParams, BatchPodcastsParams, EpisodeSort};
+use super::{
+    model, Api, ClientBuilder, EpisodeStream, Error, ErrorContext, RateLimiter, Result, RetryPolicy, SearchStream,
+    Usage,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use super::{Cassette, ResponseCache};
+use http::{header, HeaderMap};
 use reqwest::RequestBuilder;
+use serde::de::DeserializeOwned;
 use serde_json::Value;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 
-static DEFAULT_USER_AGENT: &str = "api-podcast-rust";
+pub(crate) static DEFAULT_USER_AGENT: &str = "api-podcast-rust";
 
 /// Client for accessing Listen Notes API.
-pub struct Client<'a> {
+///
+/// Cloning is cheap: clones share the HTTP connection pool and plan usage, so a single client can be
+/// stored in application state or moved into spawned tasks.
+#[derive(Clone)]
+pub struct Client {
     /// HTTP client.
     client: reqwest::Client,
     /// API context.
-    api: Api<'a>,
+    api: Api,
+    /// Base URL of API calls, defaults to the url of `api`.
+    base_url: Arc<str>,
     /// User Agent Header for API calls.
-    user_agent: &'a str,
+    user_agent: Arc<str>,
+    /// Policy for retrying failed API calls.
+    retry_policy: RetryPolicy,
+    /// Limiter throttling API calls.
+    rate_limiter: Option<RateLimiter>,
+    /// Latest plan usage reported by the API.
+    usage: Arc<Mutex<Usage>>,
+    /// Cassette recording or replaying API calls.
+    #[cfg(not(target_arch = "wasm32"))]
+    cassette: Option<Arc<Cassette>>,
+    /// Cache for responses of rarely changing endpoints.
+    #[cfg(not(target_arch = "wasm32"))]
+    cache: Option<ResponseCache>,
+    /// Timeout of each attempt, which the wasm HTTP client cannot enforce itself.
+    #[cfg(target_arch = "wasm32")]
+    timeout: Option<Duration>,
 }
 
 #[derive(Debug)]
@@ -23,19 +54,149 @@ pub struct Response {
     pub response: reqwest::Response,
     /// HTTP request that resulted in this response.
     pub request: reqwest::Request,
+    /// Number of attempts it took to get this response, including retries, or 0 if it came from the cache.
+    pub attempts: u32,
+    /// Whether this response came from the [`ResponseCache`] of the client.
+    pub cache_hit: bool,
 }
 
 impl Response {
+    /// Get plan usage from the response headers, which is empty for a [`cache_hit`](Response::cache_hit).
+    pub fn usage(&self) -> Usage {
+        Usage::from_headers(self.response.headers())
+    }
+
     /// Get JSON data object from [`reqwest::Response`].
     pub async fn json(self) -> Result<Value> {
-        Ok(self.response.json().await?)
+        self.response.json().await.map_err(Error::from)
+    }
+
+    /// Deserialize JSON data into any type, e.g. [`model::SearchResults<model::SearchPodcast>`] for a podcast search.
+    pub async fn parse<T: DeserializeOwned>(self) -> Result<T> {
+        self.response.json().await.map_err(Error::from)
+    }
+
+    /// Get typed response of [`Client::search`] and [`Client::search_episode_titles`].
+    pub async fn search_results(self) -> Result<model::SearchResults> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::typeahead`].
+    pub async fn typeahead_results(self) -> Result<model::TypeaheadResults> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::spellcheck`].
+    pub async fn spellcheck(self) -> Result<model::Spellcheck> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::fetch_related_searches`] and [`Client::fetch_trending_searches`].
+    pub async fn search_terms(self) -> Result<model::SearchTerms> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::fetch_best_podcasts`].
+    pub async fn best_podcasts(self) -> Result<model::BestPodcasts> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::fetch_podcast_by_id`].
+    pub async fn podcast(self) -> Result<model::Podcast> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::batch_fetch_podcasts`].
+    pub async fn batch_podcasts(self) -> Result<model::BatchPodcasts> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::fetch_episode_by_id`].
+    pub async fn episode(self) -> Result<model::Episode<model::Podcast>> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::batch_fetch_episodes`].
+    pub async fn batch_episodes(self) -> Result<model::BatchEpisodes> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::fetch_curated_podcasts_list_by_id`].
+    pub async fn curated_list(self) -> Result<model::CuratedList<model::Podcast>> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::fetch_curated_podcasts_lists`].
+    pub async fn curated_lists(self) -> Result<model::CuratedLists> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::fetch_podcast_genres`].
+    pub async fn genres(self) -> Result<model::Genres> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::fetch_podcast_regions`].
+    pub async fn regions(self) -> Result<model::Regions> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::fetch_podcast_languages`].
+    pub async fn languages(self) -> Result<model::Languages> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::just_listen`].
+    pub async fn random_episode(self) -> Result<model::Episode> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::fetch_recommendations_for_podcast`].
+    pub async fn podcast_recommendations(self) -> Result<model::PodcastRecommendations> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::fetch_recommendations_for_episode`].
+    pub async fn episode_recommendations(self) -> Result<model::EpisodeRecommendations> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::fetch_playlist_by_id`].
+    pub async fn playlist(self) -> Result<model::Playlist> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::fetch_my_playlists`].
+    pub async fn playlists(self) -> Result<model::Playlists> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::submit_podcast`].
+    pub async fn podcast_submission(self) -> Result<model::PodcastSubmission> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::delete_podcast`].
+    pub async fn podcast_deletion(self) -> Result<model::PodcastDeletion> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::fetch_audience_for_podcast`].
+    pub async fn audience(self) -> Result<model::Audience> {
+        self.parse().await
+    }
+
+    /// Get typed response of [`Client::fetch_podcasts_by_domain`].
+    pub async fn domain_podcasts(self) -> Result<model::DomainPodcasts> {
+        self.parse().await
     }
 }
 
-impl Client<'_> {
+impl Client {
     /// Creates new Listen API Client.
     ///
-    /// Uses default HTTP client with 30 second timeouts.
+    /// Uses default HTTP client with 30 second timeouts. The api key is copied, so the client
+    /// does not borrow it.
     ///
     /// To access production API:
     /// ```
@@ -45,47 +206,142 @@ impl Client<'_> {
     /// ```
     /// let client = podcast_api::Client::new(None);
     /// ```
+    /// To use an api key read at runtime:
+    /// ```
+    /// let key = std::env::var("LISTEN_API_KEY").ok();
+    /// let client = podcast_api::Client::new(key.as_deref());
+    /// ```
     pub fn new(id: Option<&str>) -> Client {
-        Client {
-            client: reqwest::ClientBuilder::new()
-                .timeout(Duration::from_secs(30))
-                .build()
-                .expect("Client::new()"),
-            api: if let Some(id) = id {
-                Api::Production(id)
-            } else {
-                Api::Mock
-            },
-            user_agent: DEFAULT_USER_AGENT,
+        let mut builder = Client::builder();
+        if let Some(id) = id {
+            builder = builder.api_key(id);
         }
+        builder.build().expect("Client::new()")
     }
 
     /// Creates new Listen API Client with user provided HTTP Client.
-    pub fn new_custom<'a>(client: reqwest::Client, id: Option<&'a str>, user_agent: Option<&'a str>) -> Client<'a> {
-        Client {
+    pub fn new_custom(client: reqwest::Client, id: Option<&str>, user_agent: Option<&str>) -> Client {
+        Client::from_parts(
             client,
-            api: if let Some(id) = id {
-                Api::Production(id)
+            if let Some(id) = id {
+                Api::Production(id.into())
             } else {
                 Api::Mock
             },
-            user_agent: if let Some(user_agent) = user_agent {
-                user_agent
-            } else {
-                DEFAULT_USER_AGENT
-            },
+            None,
+            user_agent.unwrap_or(DEFAULT_USER_AGENT),
+        )
+    }
+
+    /// Creates builder for configuring base URL, timeouts, proxy and headers of a new client.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let client = podcast_api::Client::builder()
+    ///     .api_key("YOUR-API-KEY")
+    ///     .base_url("http://localhost:8080/api/v2")
+    ///     .timeout(Duration::from_secs(10))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    pub(crate) fn from_parts(client: reqwest::Client, api: Api, base_url: Option<&str>, user_agent: &str) -> Client {
+        Client {
+            client,
+            base_url: base_url.unwrap_or_else(|| api.url()).trim_end_matches('/').into(),
+            api,
+            user_agent: user_agent.into(),
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None,
+            usage: Arc::new(Mutex::new(Usage::default())),
+            #[cfg(not(target_arch = "wasm32"))]
+            cassette: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            cache: None,
+            #[cfg(target_arch = "wasm32")]
+            timeout: None,
         }
     }
 
+    /// Retries failed API calls according to `retry_policy`.
+    ///
+    /// By default, failed API calls are not retried.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Throttles API calls, including retries, with `rate_limiter`.
+    ///
+    /// By default, API calls are not throttled.
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Timeout of each attempt of an API call, until the response headers arrive.
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Records API calls to `cassette`, or replays them from it, depending on how it was created.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(Arc::new(cassette));
+        self
+    }
+
+    /// Cassette set with [`with_cassette`](Client::with_cassette), e.g. to [`save`](Cassette::save) a recording.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn cassette(&self) -> Option<&Cassette> {
+        self.cassette.as_deref()
+    }
+
+    /// Caches responses of rarely changing endpoints in `cache`.
+    ///
+    /// By default, responses are not cached.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Base URL of API calls, e.g. `https://listen-api.listennotes.com/api/v2`.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Latest plan usage reported by the API across all calls made with this client and its clones.
+    pub fn usage(&self) -> Usage {
+        self.usage.lock().expect("usage lock poisoned").clone()
+    }
+
     /// Calls [`GET /search`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-search) with supplied parameters.
     pub async fn search(&self, parameters: &Value) -> Result<Response> {
         self.get("search", parameters).await
     }
 
+    /// Streams individual results of [`GET /search`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-search) with supplied parameters,
+    /// following `next_offset` across pages.
+    ///
+    /// `T` is the type of a single result, e.g. [`model::SearchEpisode`] or [`Value`].
+    pub fn search_stream<'a, T>(&'a self, parameters: &Value) -> SearchStream<'a, T>
+    where
+        T: DeserializeOwned + Send + 'a,
+    {
+        SearchStream::new(self, parameters)
+    }
+
     /// Calls [`GET /search_episode_titles`](https://www.listennotes.com/api/docs/#get-api-v2-search_episode_titles) with supplied parameters.
     pub async fn search_episode_titles(&self, parameters: &Value) -> Result<Response> {
         self.get("search_episode_titles", parameters).await
-    }    
+    }
 
     /// Calls [`GET /typeahead`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-typeahead) with supplied parameters.
     pub async fn typeahead(&self, parameters: &Value) -> Result<Response> {
@@ -117,11 +373,51 @@ impl Client<'_> {
         self.get(&format!("podcasts/{}", id), parameters).await
     }
 
+    /// Streams every episode of a podcast in `sort` order, following `next_episode_pub_date` of
+    /// [`GET /podcasts/{id}`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-podcasts-id).
+    pub fn podcast_episodes_stream<'a>(&'a self, id: &str, sort: EpisodeSort) -> EpisodeStream<'a> {
+        EpisodeStream::new(self, id, sort)
+    }
+
     /// Calls [`POST /podcasts`](https://www.listennotes.com/podcast-api/docs/#post-api-v2-podcasts) with supplied parameters.
     pub async fn batch_fetch_podcasts(&self, parameters: &Value) -> Result<Response> {
         self.post("podcasts", parameters).await
     }
 
+    /// Fetches podcasts by any number of ids with [`Client::batch_fetch_podcasts`], in chunks the API
+    /// accepts, running up to `concurrency` calls at once.
+    ///
+    /// Fails if any of the calls fails.
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// let client = podcast_api::Client::new(Some("YOUR-API-KEY"));
+    /// let ids = ["4d3fe717742d4963a85562e9f84d8c79", "ea09b575d07341599d8d5b71f205517b"];
+    /// let results = client.fetch_podcasts_by_ids(&ids, 4).await.unwrap();
+    /// println!("{} podcasts, missing {:?}", results.found.len(), results.not_found);
+    /// # });
+    /// ```
+    pub async fn fetch_podcasts_by_ids(
+        &self,
+        ids: &[&str],
+        concurrency: usize,
+    ) -> Result<BatchResults<model::Podcast>> {
+        fetch_by_ids(
+            ids,
+            concurrency,
+            |chunk| async move {
+                let parameters = BatchPodcastsParams::new().ids(&chunk).build()?;
+                Ok(self
+                    .batch_fetch_podcasts(&parameters)
+                    .await?
+                    .batch_podcasts()
+                    .await?
+                    .podcasts)
+            },
+            |podcast| &podcast.id,
+        )
+        .await
+    }
+
     /// Calls [`GET /episodes/{id}`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-episodes-id) with supplied parameters.
     pub async fn fetch_episode_by_id(&self, id: &str, parameters: &Value) -> Result<Response> {
         self.get(&format!("episodes/{}", id), parameters).await
@@ -132,6 +428,32 @@ impl Client<'_> {
         self.post("episodes", parameters).await
     }
 
+    /// Fetches episodes by any number of ids with [`Client::batch_fetch_episodes`], in chunks the API
+    /// accepts, running up to `concurrency` calls at once.
+    ///
+    /// Fails if any of the calls fails.
+    pub async fn fetch_episodes_by_ids(
+        &self,
+        ids: &[&str],
+        concurrency: usize,
+    ) -> Result<BatchResults<model::Episode>> {
+        fetch_by_ids(
+            ids,
+            concurrency,
+            |chunk| async move {
+                let parameters = BatchEpisodesParams::new(&chunk).build()?;
+                Ok(self
+                    .batch_fetch_episodes(&parameters)
+                    .await?
+                    .batch_episodes()
+                    .await?
+                    .episodes)
+            },
+            |episode| &episode.id,
+        )
+        .await
+    }
+
     /// Calls [`GET /curated_podcasts/{id}`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-curated_podcasts-id) with supplied parameters.
     pub async fn fetch_curated_podcasts_list_by_id(&self, id: &str, parameters: &Value) -> Result<Response> {
         self.get(&format!("curated_podcasts/{}", id), parameters).await
@@ -200,98 +522,210 @@ impl Client<'_> {
     /// Calls [`GET /podcasts/domains/{domain_name}`](https://www.listennotes.com/api/docs/#get-api-v2-podcasts-domains-domain_name) with supplied parameters.
     pub async fn fetch_podcasts_by_domain(&self, domain_name: &str, parameters: &Value) -> Result<Response> {
         self.get(&format!("podcasts/domains/{}", domain_name), parameters).await
-    }    
+    }
 
     async fn get(&self, endpoint: &str, parameters: &Value) -> Result<Response> {
         let request = self
             .client
-            .get(format!("{}/{}", self.api.url(), endpoint))
-            .query(parameters);
+            .get(format!("{}/{}", self.base_url, endpoint))
+            .query(&form::pairs(parameters));
+        #[cfg(not(target_arch = "wasm32"))]
+        let cache_ttl = self.cache.as_ref().and_then(|cache| cache.ttl_for(endpoint));
+        #[cfg(target_arch = "wasm32")]
+        let cache_ttl = None;
 
-        Ok(self.request(request).await?)
+        self.request(request, cache_ttl).await
     }
 
     async fn post(&self, endpoint: &str, parameters: &Value) -> Result<Response> {
         let request = self
             .client
-            .post(format!("{}/{}", self.api.url(), endpoint))
+            .post(format!("{}/{}", self.base_url, endpoint))
             .header("Content-Type", "application/x-www-form-urlencoded")
             .body(Self::urlencoded_from_json(parameters));
 
-        Ok(self.request(request).await?)
+        self.request(request, None).await
     }
 
     async fn delete(&self, endpoint: &str, parameters: &Value) -> Result<Response> {
         let request = self
             .client
-            .delete(format!("{}/{}", self.api.url(), endpoint))
-            .query(parameters);
+            .delete(format!("{}/{}", self.base_url, endpoint))
+            .query(&form::pairs(parameters));
 
-        Ok(self.request(request).await?)
+        self.request(request, None).await
     }
 
-    async fn request(&self, request: RequestBuilder) -> Result<Response> {
-        let request = if let Api::Production(key) = self.api {
-            request.header("X-ListenAPI-Key", key)
+    /// Sends `request`, caching its response for `cache_ttl` if set.
+    async fn request(
+        &self,
+        request: RequestBuilder,
+        #[cfg_attr(target_arch = "wasm32", allow(unused_variables))] cache_ttl: Option<Duration>,
+    ) -> Result<Response> {
+        let request = if let Api::Production(key) = &self.api {
+            request.header("X-ListenAPI-Key", key.as_ref())
         } else {
             request
         }
-        .header("User-Agent", self.user_agent)
+        .header("User-Agent", self.user_agent.as_ref())
         .build()?;
 
-        let response = self
-            .client
-            .execute(request.try_clone().expect(
-                "Error can remain unhandled because we're not using streams, which are the try_clone fail condition",
-            ))
-            .await;
-
-        match &response {
-            Ok(response) => match response.status() {
-                StatusCode::NOT_FOUND => return Err(Error::NotFoundError),
-                StatusCode::UNAUTHORIZED => return Err(Error::AuthenticationError),
-                StatusCode::TOO_MANY_REQUESTS => return Err(Error::RateLimitError),
-                StatusCode::BAD_REQUEST => return Err(Error::InvalidRequestError),
-                StatusCode::INTERNAL_SERVER_ERROR => return Err(Error::ListenApiError),
-                _ => {}
-            },
-            Err(err) => {
-                if err.is_connect() || err.is_timeout() {
-                    return Err(Error::ApiConnectionError);
+        #[cfg(not(target_arch = "wasm32"))]
+        let cache = match (&self.cache, cache_ttl) {
+            (Some(cache), Some(ttl)) => Some((cache, ttl, ResponseCache::key(&request))),
+            _ => None,
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some((cache, _, key)) = &cache {
+            if let Some(cached) = cache.get(key) {
+                return Ok(Response {
+                    response: cached.to_response(request.url()),
+                    request,
+                    attempts: 0,
+                    cache_hit: true,
+                });
+            }
+        }
+
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire().await;
+            }
+            let (response, retry_after) = self.execute(&request).await;
+            match response {
+                Ok(response) => {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    let response = match &cache {
+                        Some((cache, ttl, key)) => cache.insert(key, *ttl, response).await?,
+                        None => response,
+                    };
+                    return Ok(Response {
+                        response,
+                        request,
+                        attempts,
+                        cache_hit: false,
+                    });
+                }
+                Err(err) if self.retry_policy.should_retry(attempts, &err) => {
+                    futures_timer::Delay::new(self.retry_policy.delay(attempts, retry_after)).await;
+                }
+                Err(err) => return Err(err.with_attempts(attempts)),
+            }
+        }
+    }
+
+    /// Executes a single attempt of `request`, along with the delay requested by a `Retry-After` header.
+    async fn execute(&self, request: &reqwest::Request) -> (Result<reqwest::Response>, Option<Duration>) {
+        let request = request.try_clone().expect(
+            "Error can remain unhandled because we're not using streams, which are the try_clone fail condition",
+        );
+        let (method, url) = (request.method().clone(), request.url().clone());
+        let from_send = |err| Error::from_send(&method, &url, err);
+        #[cfg(not(target_arch = "wasm32"))]
+        let response = match &self.cassette {
+            Some(cassette) => cassette.execute(&self.client, request).await,
+            None => self.client.execute(request).await.map_err(from_send),
+        };
+        #[cfg(target_arch = "wasm32")]
+        let response = match self.timeout {
+            Some(timeout) => {
+                let response = self.client.execute(request);
+                futures::pin_mut!(response);
+                match futures::future::select(response, futures_timer::Delay::new(timeout)).await {
+                    futures::future::Either::Left((response, _)) => response.map_err(from_send),
+                    futures::future::Either::Right(_) => Err(Error::connection(&method, &url, None)),
                 }
             }
+            None => self.client.execute(request).await.map_err(from_send),
         };
 
-        Ok(Response {
-            response: response?,
-            request,
-        })
-    }
-
-    fn urlencoded_from_json(json: &Value) -> String {
-        if let Some(v) = json.as_object() {
-            v.iter()
-                .map(|(key, value)| {
-                    format!(
-                        "{}={}",
-                        key,
-                        match value {
-                            Value::String(s) => s.to_owned(), // serde_json String(_) formatter includes the quotations marks, this doesn't
-                            _ => format!("{}", value),
-                        }
-                    )
-                })
-                .collect::<Vec<String>>()
-                .join("&")
-        } else {
-            String::new()
+        if let Ok(response) = &response {
+            self.usage
+                .lock()
+                .expect("usage lock poisoned")
+                .update(Usage::from_headers(response.headers()));
         }
+        let retry_after = response
+            .as_ref()
+            .ok()
+            .and_then(|response| retry_after(response.headers()));
+        let response = match response {
+            Ok(response) if response.status().is_success() => Ok(response),
+            Ok(response) => Err(Error::from_context(ErrorContext::from_response(response).await)),
+            Err(err) => Err(err),
+        };
+
+        (response, retry_after)
     }
+
+    pub(crate) fn urlencoded_from_json(json: &Value) -> String {
+        form::encode(json)
+    }
+}
+
+/// Rebuilds response from its recorded or cached parts.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn response_from_parts(
+    url: &reqwest::Url,
+    status: u16,
+    headers: &[(String, String)],
+    body: impl Into<reqwest::Body>,
+) -> std::result::Result<reqwest::Response, http::Error> {
+    use reqwest::ResponseBuilderExt;
+
+    let mut response = http::Response::builder().status(status).url(url.clone());
+    for (name, value) in headers {
+        response = response.header(name.as_str(), value.as_str());
+    }
+    response.body(body.into()).map(reqwest::Response::from)
+}
+
+/// Headers with valid string values, in order.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn header_pairs(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_owned())))
+        .collect()
+}
+
+/// Parses `Retry-After` header, which is either a number of seconds or an HTTP date.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(header::RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(now()).unwrap_or_default())
+}
+
+/// Current time, from the JavaScript clock on wasm, where `SystemTime::now` panics.
+pub(crate) fn now() -> SystemTime {
+    #[cfg(target_arch = "wasm32")]
+    return SystemTime::UNIX_EPOCH + Duration::from_secs_f64(js_sys::Date::now() / 1000.0);
+    #[cfg(not(target_arch = "wasm32"))]
+    SystemTime::now()
 }
 
 #[cfg(test)]
 mod tests {
+    use http::{header, HeaderMap, HeaderValue};
     use serde_json::json;
+    use std::time::{Duration, SystemTime};
+    #[test]
+    fn owned_client() {
+        fn assert_shareable<T: Clone + Send + Sync + 'static>(_: &T) {}
+        let key = String::from("YOUR-API-KEY");
+        let client = super::Client::new(Some(&key));
+        drop(key);
+        assert_shareable(&client);
+        let clone = client.clone();
+        clone.usage.lock().unwrap().usage = Some(1);
+        assert_eq!(client.usage().usage, Some(1));
+    }
+
     #[test]
     fn urlencoded_from_json() {
         assert_eq!(
@@ -300,7 +734,23 @@ mod tests {
                 "b": true,
                 "c": "test_string"
             })),
-            "a=1&b=true&c=test_string"
+            "a=1&b=1&c=test_string"
+        );
+    }
+
+    #[test]
+    fn retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(super::retry_after(&headers), None);
+        headers.insert(header::RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(super::retry_after(&headers), Some(Duration::from_secs(120)));
+        headers.insert(
+            header::RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
         );
+        assert_eq!(super::retry_after(&headers), Some(Duration::from_secs(0)));
+        let date = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(60));
+        headers.insert(header::RETRY_AFTER, HeaderValue::from_str(&date).unwrap());
+        assert!(super::retry_after(&headers).unwrap() > Duration::from_secs(50));
     }
 }
@@ -0,0 +1,364 @@
+use super::cache::fnv1a;
+use super::{CacheStore, CachedResponse, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+static EXTENSION: &str = "entry";
+
+/// [`CacheStore`] keeping one file per response in a directory, so cached responses survive restarts.
+///
+/// When the directory grows beyond its limits, expired responses are removed first, then the least
+/// recently used ones. The directory can be shared by several processes, though files written by others
+/// are only counted towards the limits once this store has to evict. Failures to read or write files are
+/// treated as cache misses.
+///
+/// Files are read and written with blocking `std::fs` calls on the task making the API call, since
+/// [`CacheStore`] is synchronous and the client doesn't depend on an async runtime. Keep the directory
+/// on a local disk so these calls stay short.
+/// ```no_run
+/// use std::time::Duration;
+///
+/// let store = podcast_api::DiskStore::new("/var/cache/podcasts")
+///     .unwrap()
+///     .max_bytes(50 * 1024 * 1024);
+/// let client = podcast_api::Client::new(None).with_cache(
+///     podcast_api::ResponseCache::with_store(store)
+///         .ttl("podcasts/*", Duration::from_secs(24 * 60 * 60))
+///         .ttl("episodes/*", Duration::from_secs(24 * 60 * 60)),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct DiskStore {
+    dir: PathBuf,
+    max_bytes: u64,
+    max_entries: usize,
+    /// Size and number of the stored files, shared by clones so only evicting has to list the directory.
+    totals: Arc<Mutex<Totals>>,
+}
+
+#[derive(Debug, Default)]
+struct Totals {
+    bytes: u64,
+    entries: usize,
+}
+
+/// First line of an entry file, followed by the response body.
+#[derive(Debug, Serialize, Deserialize)]
+struct Metadata {
+    key: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+    /// Seconds since the Unix epoch.
+    expires_at: u64,
+}
+
+impl DiskStore {
+    /// Creates store in `dir`, creating the directory if needed, for up to 100 MiB in 10000 responses.
+    pub fn new(dir: impl Into<PathBuf>) -> Result<DiskStore> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        let store = DiskStore {
+            dir,
+            max_bytes: 100 * 1024 * 1024,
+            max_entries: 10_000,
+            totals: Arc::default(),
+        };
+        store.recount(&store.entries());
+        Ok(store)
+    }
+
+    /// Maximum total size of stored files.
+    pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Maximum number of stored responses.
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// Directory of the stored files.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Removes all stored responses.
+    pub fn clear(&self) -> Result<()> {
+        for (path, size, _) in self.entries() {
+            fs::remove_file(path)?;
+            self.remove_from_totals(size);
+        }
+        Ok(())
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.{}", fnv1a(key.as_bytes()), EXTENSION))
+    }
+
+    fn read(&self, key: &str) -> Option<CachedResponse> {
+        // Opened with write access, which Windows requires for updating the modification time below.
+        let file = OpenOptions::new().read(true).write(true).open(self.path(key)).ok()?;
+        let mut reader = BufReader::new(&file);
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let metadata: Metadata = serde_json::from_str(&line).ok()?;
+        if metadata.key != key {
+            return None;
+        }
+        let mut body = Vec::new();
+        reader.read_to_end(&mut body).ok()?;
+        // Marks the entry as recently used for eviction; failing to only makes it evicted sooner.
+        file.set_modified(SystemTime::now()).ok();
+        Some(CachedResponse {
+            status: metadata.status,
+            headers: metadata.headers,
+            body,
+            expires_at: UNIX_EPOCH + Duration::from_secs(metadata.expires_at),
+        })
+    }
+
+    fn write(&self, key: &str, response: CachedResponse) -> Result<()> {
+        let metadata = Metadata {
+            key: key.to_owned(),
+            status: response.status,
+            headers: response.headers,
+            expires_at: response
+                .expires_at
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |expires_at| expires_at.as_secs()),
+        };
+        let metadata = serde_json::to_vec(&metadata)?;
+        let path = self.path(key);
+        // Written next to the entry and renamed, so other processes never read partial entries.
+        let partial = path.with_extension(format!("{}.{}", std::process::id(), fnv1a(&response.body)));
+        let mut file = File::create(&partial)?;
+        file.write_all(&metadata)?;
+        file.write_all(b"\n")?;
+        file.write_all(&response.body)?;
+        drop(file);
+        let replaced = fs::metadata(&path).ok().map(|replaced| replaced.len());
+        fs::rename(&partial, &path).inspect_err(|_| {
+            fs::remove_file(&partial).ok();
+        })?;
+
+        let mut totals = self.totals();
+        if let Some(size) = replaced {
+            totals.bytes = totals.bytes.saturating_sub(size);
+            totals.entries = totals.entries.saturating_sub(1);
+        }
+        totals.bytes += (metadata.len() + 1 + response.body.len()) as u64;
+        totals.entries += 1;
+        Ok(())
+    }
+
+    fn totals(&self) -> std::sync::MutexGuard<'_, Totals> {
+        self.totals.lock().expect("disk store lock poisoned")
+    }
+
+    fn remove_from_totals(&self, size: u64) {
+        let mut totals = self.totals();
+        totals.bytes = totals.bytes.saturating_sub(size);
+        totals.entries = totals.entries.saturating_sub(1);
+    }
+
+    /// Resets the totals to the listed `entries`.
+    fn recount(&self, entries: &[(PathBuf, u64, SystemTime)]) {
+        *self.totals() = Totals {
+            bytes: entries.iter().map(|(_, size, _)| size).sum(),
+            entries: entries.len(),
+        };
+    }
+
+    fn within_limits(&self, bytes: u64, entries: usize) -> bool {
+        bytes <= self.max_bytes && entries <= self.max_entries
+    }
+
+    /// Entry files with their size and last use.
+    fn entries(&self) -> Vec<(PathBuf, u64, SystemTime)> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+        entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != EXTENSION {
+                    return None;
+                }
+                let metadata = fs::metadata(&path).ok()?;
+                Some((path, metadata.len(), metadata.modified().ok()?))
+            })
+            .collect()
+    }
+
+    /// Removes expired and then least recently used entries until within limits.
+    ///
+    /// Only lists the directory once the totals exceed a limit, and then recounts them from the listing,
+    /// which includes files written by other processes.
+    fn evict(&self) {
+        {
+            let totals = self.totals();
+            if self.within_limits(totals.bytes, totals.entries) {
+                return;
+            }
+        }
+        let mut entries = self.entries();
+        let mut bytes: u64 = entries.iter().map(|(_, size, _)| size).sum();
+        if self.within_limits(bytes, entries.len()) {
+            self.recount(&entries);
+            return;
+        }
+
+        let now = SystemTime::now();
+        entries.retain(|(path, size, _)| {
+            if expired(path, now) && fs::remove_file(path).is_ok() {
+                bytes -= size;
+                false
+            } else {
+                true
+            }
+        });
+
+        entries.sort_by_key(|(_, _, used)| *used);
+        let mut count = entries.len();
+        for (path, size, _) in entries {
+            if self.within_limits(bytes, count) {
+                break;
+            }
+            if fs::remove_file(path).is_ok() {
+                bytes -= size;
+                count -= 1;
+            }
+        }
+        *self.totals() = Totals { bytes, entries: count };
+    }
+}
+
+impl CacheStore for DiskStore {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        self.read(key)
+    }
+
+    fn insert(&self, key: &str, response: CachedResponse) {
+        if self.write(key, response).is_ok() {
+            self.evict();
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let path = self.path(key);
+        if let Ok(metadata) = fs::metadata(&path) {
+            if fs::remove_file(path).is_ok() {
+                self.remove_from_totals(metadata.len());
+            }
+        }
+    }
+}
+
+fn expired(path: &Path, now: SystemTime) -> bool {
+    let metadata = File::open(path).ok().and_then(|file| {
+        let mut line = String::new();
+        BufReader::new(file).read_line(&mut line).ok()?;
+        serde_json::from_str::<Metadata>(&line).ok()
+    });
+    match metadata {
+        Some(metadata) => UNIX_EPOCH + Duration::from_secs(metadata.expires_at) <= now,
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(name: &str) -> DiskStore {
+        let dir = std::env::temp_dir().join(format!("podcast-api-{}-{}", name, std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        DiskStore::new(dir).unwrap()
+    }
+
+    fn response(body: &str, ttl: Duration) -> CachedResponse {
+        CachedResponse {
+            status: 200,
+            headers: vec![("content-type".to_owned(), "application/json".to_owned())],
+            body: body.as_bytes().to_vec(),
+            expires_at: UNIX_EPOCH
+                + Duration::from_secs((SystemTime::now() + ttl).duration_since(UNIX_EPOCH).unwrap().as_secs()),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let store = store("round-trip");
+        let cached = response("{\"genres\": []}\n", Duration::from_secs(60));
+        store.insert("GET https://example.com/api/v2/genres", cached.clone());
+        assert_eq!(store.get("GET https://example.com/api/v2/genres"), Some(cached.clone()));
+
+        let restarted = DiskStore::new(store.dir()).unwrap();
+        assert_eq!(restarted.get("GET https://example.com/api/v2/genres"), Some(cached));
+        assert_eq!(restarted.get("GET https://example.com/api/v2/regions"), None);
+
+        restarted.remove("GET https://example.com/api/v2/genres");
+        assert_eq!(store.get("GET https://example.com/api/v2/genres"), None);
+        fs::remove_dir_all(store.dir()).unwrap();
+    }
+
+    #[test]
+    fn totals() {
+        let store = store("totals");
+        let listed = |store: &DiskStore| {
+            let entries = store.entries();
+            (entries.iter().map(|(_, size, _)| size).sum::<u64>(), entries.len())
+        };
+        store.insert("a", response("{}", Duration::from_secs(60)));
+        store.insert("b", response("{}", Duration::from_secs(60)));
+        store.insert("a", response("{\"genres\": []}", Duration::from_secs(60)));
+        store.remove("b");
+        let totals = store.totals();
+        assert_eq!((totals.bytes, totals.entries), listed(&store));
+        drop(totals);
+
+        let restarted = DiskStore::new(store.dir()).unwrap();
+        let totals = restarted.totals();
+        assert_eq!((totals.bytes, totals.entries), listed(&store));
+        drop(totals);
+        fs::remove_dir_all(store.dir()).unwrap();
+    }
+
+    #[test]
+    fn eviction() {
+        let store = store("eviction").max_entries(2);
+        store.insert("expired", response("{}", Duration::from_secs(0)));
+        store.insert("a", response("{}", Duration::from_secs(60)));
+        store.insert("b", response("{}", Duration::from_secs(60)));
+        assert_eq!(store.get("expired"), None);
+        assert!(store.get("a").is_some());
+        assert!(store.get("b").is_some());
+
+        store.clear().unwrap();
+        assert_eq!(store.entries().len(), 0);
+        let store = store.max_entries(2);
+        store.insert("a", response("{}", Duration::from_secs(60)));
+        store.insert("b", response("{}", Duration::from_secs(60)));
+        for (key, used) in [("a", 1), ("b", 2)] {
+            let file = OpenOptions::new().write(true).open(store.path(key)).unwrap();
+            file.set_modified(UNIX_EPOCH + Duration::from_secs(used)).unwrap();
+        }
+        assert!(store.get("a").is_some());
+        store.insert("c", response("{}", Duration::from_secs(60)));
+        assert!(store.get("a").is_some());
+        assert_eq!(store.get("b"), None);
+
+        store.clear().unwrap();
+        let store = store.max_bytes(1);
+        store.insert("a", response("{}", Duration::from_secs(60)));
+        assert_eq!(store.entries().len(), 0);
+        fs::remove_dir_all(store.dir()).unwrap();
+    }
+}
@@ -1,23 +1,229 @@
+use http::{Method, StatusCode};
+use serde_json::Value;
+
 /// Error for API calls from [`Client`](super::Client).
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// Wrong api key or your account is suspended.
-    AuthenticationError,
-    /// Fail to connect to API servers.
-    ApiConnectionError,
+    AuthenticationError(ErrorContext),
+    /// Fail to connect to API servers, or no response before the timeout.
+    ApiConnectionError {
+        /// HTTP method of the request.
+        method: Method,
+        /// Path of the endpoint that was called, e.g. `/api/v2/search`.
+        endpoint: String,
+        /// Error from http client, if any.
+        source: Option<reqwest::Error>,
+        /// Number of attempts of the call, including retries.
+        attempts: u32,
+    },
     /// Something wrong on your end (client side errors), e.g., missing required parameters.
-    InvalidRequestError,
+    InvalidRequestError(ErrorContext),
     /// For FREE plan, exceeding the quota limit; or for all plans, sending too many requests
     /// too fast and exceeding the rate limit - https://www.listennotes.com/api/faq/#faq17
-    RateLimitError,
+    RateLimitError(ErrorContext),
     /// Endpoint not exist, or podcast / episode not exist.
-    NotFoundError,
+    NotFoundError(ErrorContext),
     /// Something wrong on our end (unexpected server errors).
-    ListenApiError,
+    ListenApiError(ErrorContext),
+    /// Invalid response from an upstream server (HTTP 502).
+    BadGatewayError(ErrorContext),
+    /// API servers are temporarily unavailable, e.g. during maintenance (HTTP 503).
+    ServiceUnavailableError(ErrorContext),
+    /// No timely response from an upstream server (HTTP 504).
+    GatewayTimeoutError(ErrorContext),
+    /// Any other unsuccessful HTTP status, e.g. 403.
+    UnexpectedStatus(ErrorContext),
+    /// Invalid request parameters, detected before sending the request.
+    ValidationError(String),
+    /// No recorded response for a request replayed from a [`Cassette`](super::Cassette), or an invalid recording.
+    CassetteError(String),
     /// Error from http client.
     Reqwest(reqwest::Error),
     /// Error from JSON creation/processing.
     Json(serde_json::Error),
+    /// Error from reading or writing files.
+    Io(std::io::Error),
+}
+
+/// Details of a failed API call, carried by the API error variants of [`Error`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorContext {
+    /// HTTP status code of the response.
+    pub status: StatusCode,
+    /// Response body, parsed as JSON if possible and as a JSON string otherwise.
+    pub body: Option<Value>,
+    /// Path of the endpoint that was called, e.g. `/api/v2/search`.
+    pub endpoint: String,
+    /// Unique id of the request from the `X-Request-ID` header, to include when contacting support.
+    pub request_id: Option<String>,
+    /// Number of attempts of the call, including retries according to [`RetryPolicy`](super::RetryPolicy).
+    pub attempts: u32,
+}
+
+impl ErrorContext {
+    pub(crate) async fn from_response(response: reqwest::Response) -> ErrorContext {
+        let (status, url, headers) = (response.status(), response.url().clone(), response.headers().clone());
+        ErrorContext::new(status, &url, &headers, response.text().await.ok())
+    }
+
+    #[cfg(feature = "blocking")]
+    pub(crate) fn from_blocking_response(response: reqwest::blocking::Response) -> ErrorContext {
+        let (status, url, headers) = (response.status(), response.url().clone(), response.headers().clone());
+        ErrorContext::new(status, &url, &headers, response.text().ok())
+    }
+
+    fn new(status: StatusCode, url: &reqwest::Url, headers: &http::HeaderMap, body: Option<String>) -> ErrorContext {
+        ErrorContext {
+            status,
+            body: body
+                .filter(|body| !body.trim().is_empty())
+                .map(|body| serde_json::from_str(&body).unwrap_or(Value::String(body))),
+            endpoint: url.path().to_owned(),
+            request_id: headers
+                .get("x-request-id")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned),
+            attempts: 1,
+        }
+    }
+
+    /// Error message from the response body, if any.
+    pub fn message(&self) -> Option<&str> {
+        match self.body.as_ref()? {
+            Value::String(message) => Some(message),
+            body => ["message", "error", "detail"]
+                .iter()
+                .find_map(|key| body.get(key).and_then(Value::as_str)),
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", self.status, self.endpoint)?;
+        if let Some(request_id) = &self.request_id {
+            write!(f, ", request id {}", request_id)?;
+        }
+        if let Some(message) = self.message() {
+            write!(f, ": {}", message)?;
+        }
+        if self.attempts > 1 {
+            write!(f, ", after {} attempts", self.attempts)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error {
+    /// Maps the status of an unsuccessful response to its error.
+    pub(crate) fn from_context(context: ErrorContext) -> Error {
+        match context.status {
+            StatusCode::BAD_REQUEST => Error::InvalidRequestError(context),
+            StatusCode::UNAUTHORIZED => Error::AuthenticationError(context),
+            StatusCode::NOT_FOUND => Error::NotFoundError(context),
+            StatusCode::TOO_MANY_REQUESTS => Error::RateLimitError(context),
+            StatusCode::INTERNAL_SERVER_ERROR => Error::ListenApiError(context),
+            StatusCode::BAD_GATEWAY => Error::BadGatewayError(context),
+            StatusCode::SERVICE_UNAVAILABLE => Error::ServiceUnavailableError(context),
+            StatusCode::GATEWAY_TIMEOUT => Error::GatewayTimeoutError(context),
+            _ => Error::UnexpectedStatus(context),
+        }
+    }
+
+    /// Maps failure to send a `method` request to `url`, treating connection failures and timeouts as
+    /// [`Error::ApiConnectionError`].
+    pub(crate) fn from_send(method: &Method, url: &reqwest::Url, err: reqwest::Error) -> Error {
+        #[cfg(not(target_arch = "wasm32"))]
+        let connect = err.is_connect();
+        // The wasm HTTP client reports failed fetches, e.g. when offline, as request errors.
+        #[cfg(target_arch = "wasm32")]
+        let connect = err.is_request();
+        if connect || err.is_timeout() {
+            Error::connection(method, url, Some(err))
+        } else {
+            Error::Reqwest(err)
+        }
+    }
+
+    /// [`Error::ApiConnectionError`] for a `method` request to `url`.
+    pub(crate) fn connection(method: &Method, url: &reqwest::Url, source: Option<reqwest::Error>) -> Error {
+        Error::ApiConnectionError {
+            method: method.clone(),
+            endpoint: url.path().to_owned(),
+            source,
+            attempts: 1,
+        }
+    }
+
+    /// Records that the call failed after `attempts` attempts.
+    pub(crate) fn with_attempts(mut self, attempts: u32) -> Error {
+        match &mut self {
+            Error::ApiConnectionError { attempts: count, .. } => *count = attempts,
+            Error::AuthenticationError(context)
+            | Error::InvalidRequestError(context)
+            | Error::RateLimitError(context)
+            | Error::NotFoundError(context)
+            | Error::ListenApiError(context)
+            | Error::BadGatewayError(context)
+            | Error::ServiceUnavailableError(context)
+            | Error::GatewayTimeoutError(context)
+            | Error::UnexpectedStatus(context) => context.attempts = attempts,
+            _ => {}
+        }
+        self
+    }
+
+    /// Number of attempts of the failed API call, including retries, if it was sent.
+    pub fn attempts(&self) -> Option<u32> {
+        match self {
+            Error::ApiConnectionError { attempts, .. } => Some(*attempts),
+            _ => self.context().map(|context| context.attempts),
+        }
+    }
+
+    /// Details of the failed API call, if the API responded.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Error::AuthenticationError(context)
+            | Error::InvalidRequestError(context)
+            | Error::RateLimitError(context)
+            | Error::NotFoundError(context)
+            | Error::ListenApiError(context)
+            | Error::BadGatewayError(context)
+            | Error::ServiceUnavailableError(context)
+            | Error::GatewayTimeoutError(context)
+            | Error::UnexpectedStatus(context) => Some(context),
+            _ => None,
+        }
+    }
+
+    /// HTTP status code of the failed API call, if the API responded.
+    pub fn status(&self) -> Option<StatusCode> {
+        self.context().map(|context| context.status)
+    }
+
+    /// Whether the same call may succeed when retried later, e.g. after a rate limit or a server error.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Error::RateLimitError(_)
+                | Error::ListenApiError(_)
+                | Error::BadGatewayError(_)
+                | Error::ServiceUnavailableError(_)
+                | Error::GatewayTimeoutError(_)
+                | Error::ApiConnectionError { .. }
+        )
+    }
+
+    /// Whether the call failed because of the request itself, e.g. invalid parameters or api key.
+    pub fn is_client_error(&self) -> bool {
+        match self {
+            Error::ValidationError(_) => true,
+            _ => matches!(self.status(), Some(status) if status.is_client_error()),
+        }
+    }
 }
 
 impl From<reqwest::Error> for Error {
@@ -32,11 +238,19 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match *self {
+            Error::ApiConnectionError { ref source, .. } => source.as_ref().map(|e| e as _),
             Error::Reqwest(ref e) => Some(e),
             Error::Json(ref e) => Some(e),
+            Error::Io(ref e) => Some(e),
             _ => None,
         }
     }
@@ -45,35 +259,163 @@ impl std::error::Error for Error {
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            Error::AuthenticationError => {
-                write!(f, "Wrong api key or your account is suspended.")
+            Error::AuthenticationError(context) => {
+                write!(f, "Wrong api key or your account is suspended. ({})", context)
+            }
+            Error::ApiConnectionError {
+                method,
+                endpoint,
+                source,
+                attempts,
+            } => {
+                write!(f, "Fail to connect to API servers. ({} {}", method, endpoint)?;
+                if let Some(source) = source {
+                    write!(f, ": {}", source)?;
+                }
+                if *attempts > 1 {
+                    write!(f, ", after {} attempts", attempts)?;
+                }
+                write!(f, ")")
             }
-            Error::ApiConnectionError => {
-                write!(f, "Fail to connect to API servers.")
+            Error::InvalidRequestError(context) => {
+                write!(
+                    f,
+                    "Something wrong on your end (client side errors), e.g., missing required parameters. ({})",
+                    context
+                )
             }
-            Error::InvalidRequestError => {
+            Error::RateLimitError(context) => {
                 write!(
                     f,
-                    "Something wrong on your end (client side errors), e.g., missing required parameters."
+                    "For FREE plan, exceeding the quota limit; or for all plans, sending too many requests too fast \
+                     and exceeding the rate limit - https://www.listennotes.com/api/faq/#faq17 ({})",
+                    context
                 )
             }
-            Error::RateLimitError => {
+            Error::NotFoundError(context) => {
+                write!(f, "Endpoint not exist, or podcast / episode not exist. ({})", context)
+            }
+            Error::ListenApiError(context) => {
                 write!(
                     f,
-                    "For FREE plan, exceeding the quota limit; or for all plans, sending too many 
-                    requests too fast and exceeding the rate limit 
-                    - https://www.listennotes.com/api/faq/#faq17"
+                    "Something wrong on our end (unexpected server errors). ({})",
+                    context
                 )
             }
-            Error::NotFoundError => {
-                write!(f, "Endpoint not exist, or podcast / episode not exist.")
+            Error::BadGatewayError(context) => {
+                write!(f, "Invalid response from an upstream server. ({})", context)
+            }
+            Error::ServiceUnavailableError(context) => {
+                write!(f, "API servers are temporarily unavailable. ({})", context)
             }
-            Error::ListenApiError => {
-                write!(f, "Something wrong on our end (unexpected server errors).")
+            Error::GatewayTimeoutError(context) => {
+                write!(f, "No timely response from an upstream server. ({})", context)
             }
-            Error::Reqwest(_) | Error::Json(_) => {
-                write!(f, "{}", self)
+            Error::UnexpectedStatus(context) => {
+                write!(f, "Unexpected response status. ({})", context)
             }
+            Error::ValidationError(message) => {
+                write!(f, "Invalid request parameters: {}", message)
+            }
+            Error::CassetteError(message) => {
+                write!(f, "Cassette error: {}", message)
+            }
+            Error::Reqwest(e) => {
+                write!(f, "{}", e)
+            }
+            Error::Json(e) => {
+                write!(f, "{}", e)
+            }
+            Error::Io(e) => {
+                write!(f, "{}", e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn context(status: StatusCode, body: Option<Value>) -> ErrorContext {
+        ErrorContext {
+            status,
+            body,
+            endpoint: "/api/v2/search".to_owned(),
+            request_id: Some("abc123".to_owned()),
+            attempts: 1,
         }
     }
+
+    #[test]
+    fn display() {
+        let err = Error::NotFoundError(context(
+            StatusCode::NOT_FOUND,
+            Some(json!({ "error": "No such podcast" })),
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Endpoint not exist, or podcast / episode not exist. \
+             (404 Not Found /api/v2/search, request id abc123: No such podcast)"
+        );
+        let err = Error::Json(serde_json::from_str::<Value>("{").unwrap_err());
+        assert_eq!(err.to_string(), "EOF while parsing an object at line 1 column 1");
+        let url = reqwest::Url::parse("https://listen-api.listennotes.com/api/v2/search?q=star").unwrap();
+        let err = Error::connection(&Method::GET, &url, None);
+        assert_eq!(err.to_string(), "Fail to connect to API servers. (GET /api/v2/search)");
+        assert!(std::error::Error::source(&err).is_none());
+    }
+
+    #[test]
+    fn predicates() {
+        let rate_limit = Error::RateLimitError(context(StatusCode::TOO_MANY_REQUESTS, None));
+        assert!(rate_limit.is_retryable());
+        assert!(rate_limit.is_client_error());
+        assert_eq!(rate_limit.status(), Some(StatusCode::TOO_MANY_REQUESTS));
+
+        let server = Error::ListenApiError(context(StatusCode::INTERNAL_SERVER_ERROR, None));
+        assert!(server.is_retryable());
+        assert!(!server.is_client_error());
+
+        let not_found = Error::NotFoundError(context(StatusCode::NOT_FOUND, None)).with_attempts(2);
+        assert!(!not_found.is_retryable());
+        assert!(not_found.is_client_error());
+        assert_eq!(not_found.status(), Some(StatusCode::NOT_FOUND));
+        assert_eq!(not_found.attempts(), Some(2));
+        assert!(not_found.to_string().ends_with("/api/v2/search, request id abc123, after 2 attempts)"));
+
+        assert!(Error::ValidationError("q is required".to_owned()).is_client_error());
+        let url = reqwest::Url::parse("https://listen-api.listennotes.com/api/v2/search").unwrap();
+        assert!(Error::connection(&Method::GET, &url, None).is_retryable());
+    }
+
+    #[test]
+    fn from_context() {
+        let err = |status| Error::from_context(context(status, None));
+        assert!(matches!(err(StatusCode::BAD_REQUEST), Error::InvalidRequestError(_)));
+        assert!(matches!(err(StatusCode::UNAUTHORIZED), Error::AuthenticationError(_)));
+        assert!(matches!(err(StatusCode::NOT_FOUND), Error::NotFoundError(_)));
+        assert!(matches!(err(StatusCode::TOO_MANY_REQUESTS), Error::RateLimitError(_)));
+        assert!(matches!(
+            err(StatusCode::INTERNAL_SERVER_ERROR),
+            Error::ListenApiError(_)
+        ));
+        assert!(matches!(err(StatusCode::BAD_GATEWAY), Error::BadGatewayError(_)));
+        assert!(matches!(
+            err(StatusCode::SERVICE_UNAVAILABLE),
+            Error::ServiceUnavailableError(_)
+        ));
+        assert!(matches!(
+            err(StatusCode::GATEWAY_TIMEOUT),
+            Error::GatewayTimeoutError(_)
+        ));
+
+        let forbidden = err(StatusCode::FORBIDDEN);
+        assert!(matches!(forbidden, Error::UnexpectedStatus(_)));
+        assert!(forbidden.is_client_error());
+        assert!(!forbidden.is_retryable());
+        assert!(err(StatusCode::SERVICE_UNAVAILABLE).is_retryable());
+        assert!(matches!(err(StatusCode::NOT_MODIFIED), Error::UnexpectedStatus(_)));
+    }
 }
@@ -0,0 +1,374 @@
+use super::client::response_from_parts;
+use super::{form, Error, ErrorContext, PodcastApi, Response, Result};
+use http::{Method, StatusCode};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+static BASE_URL: &str = "https://fake.listennotes.invalid/api/v2";
+
+/// In-memory [`PodcastApi`] with programmable responses, recording every call, to unit test code using the
+/// API deterministically.
+///
+/// Responses are programmed for endpoints like `search` or `podcasts/*`, where `*` matches any single path
+/// segment, optionally prefixed by the HTTP method, e.g. `DELETE podcasts/*`. Responses queued with
+/// [`respond_once`](FakeClient::respond_once) are used before the ones set with [`respond`](FakeClient::respond).
+/// Calls without a programmed response fail with [`Error::NotFoundError`]. Clones share their responses and calls.
+/// ```
+/// # tokio_test::block_on(async {
+/// use podcast_api::{FakeClient, PodcastApi};
+/// use serde_json::json;
+///
+/// let fake = FakeClient::new();
+/// fake.respond("search", json!({ "count": 0, "total": 0, "results": [] }));
+/// fake.respond_once("search", http::StatusCode::TOO_MANY_REQUESTS, json!({ "message": "Too many requests" }));
+///
+/// let response = fake.search(&json!({ "q": "startup" })).await;
+/// assert!(matches!(response, Err(podcast_api::Error::RateLimitError(_))));
+/// let response = fake.search(&json!({ "q": "startup" })).await.unwrap();
+/// assert_eq!(response.json().await.unwrap()["total"], 0);
+///
+/// assert_eq!(fake.calls().len(), 2);
+/// assert_eq!(fake.calls()[0].parameters, json!({ "q": "startup" }));
+/// # });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FakeClient {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Responses for every call, by endpoint pattern.
+    responses: Vec<(String, StatusCode, Value)>,
+    /// Responses for a single call, by endpoint pattern.
+    queued: VecDeque<(String, StatusCode, Value)>,
+    calls: Vec<FakeCall>,
+}
+
+/// API call made through a [`FakeClient`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FakeCall {
+    /// HTTP method of the endpoint.
+    pub method: Method,
+    /// Called endpoint, e.g. `podcasts/4d3fe717742d4963a85562e9f84d8c79`.
+    pub endpoint: String,
+    /// Supplied parameters.
+    pub parameters: Value,
+}
+
+impl FakeClient {
+    /// Creates fake without any programmed responses.
+    pub fn new() -> FakeClient {
+        Default::default()
+    }
+
+    /// Responds to every call of `endpoint` with `body`, replacing any earlier response for `endpoint`.
+    ///
+    /// When the patterns of several responses match a call, the one set last is used.
+    pub fn respond(&self, endpoint: &str, body: Value) {
+        let mut state = self.state();
+        state.responses.retain(|(pattern, _, _)| pattern != endpoint);
+        state.responses.push((endpoint.to_owned(), StatusCode::OK, body));
+    }
+
+    /// Responds to the next call of `endpoint` with `status` and `body`, after any earlier queued responses.
+    ///
+    /// Statuses other than 2xx fail the call with the matching [`Error`], like the API would.
+    pub fn respond_once(&self, endpoint: &str, status: StatusCode, body: Value) {
+        self.state().queued.push_back((endpoint.to_owned(), status, body));
+    }
+
+    /// Calls made so far, in order.
+    pub fn calls(&self) -> Vec<FakeCall> {
+        self.state().calls.clone()
+    }
+
+    fn call(
+        &self,
+        method: Method,
+        endpoint: &str,
+        parameters: &Value,
+    ) -> impl Future<Output = Result<Response>> + Send {
+        // Recorded and answered when awaited, like a real call is only sent then.
+        let (fake, endpoint, parameters) = (self.clone(), endpoint.to_owned(), parameters.clone());
+        async move { fake.response(method, &endpoint, &parameters) }
+    }
+
+    fn response(&self, method: Method, endpoint: &str, parameters: &Value) -> Result<Response> {
+        let (status, body) = {
+            let mut state = self.state();
+            state.calls.push(FakeCall {
+                method: method.clone(),
+                endpoint: endpoint.to_owned(),
+                parameters: parameters.clone(),
+            });
+            let queued = state
+                .queued
+                .iter()
+                .position(|(pattern, _, _)| matches(pattern, &method, endpoint));
+            match queued {
+                Some(index) => state.queued.remove(index).map(|(_, status, body)| (status, body)),
+                None => state
+                    .responses
+                    .iter()
+                    .rev()
+                    .find(|(pattern, _, _)| matches(pattern, &method, endpoint))
+                    .map(|(_, status, body)| (*status, body.clone())),
+            }
+        }
+        .unwrap_or_else(|| {
+            let message = format!("No fake response for {} {}", method, endpoint);
+            (StatusCode::NOT_FOUND, json!({ "message": message }))
+        });
+
+        let mut url = reqwest::Url::parse(&format!("{}/{}", BASE_URL, endpoint))
+            .map_err(|err| Error::ValidationError(format!("invalid endpoint {}: {}", endpoint, err)))?;
+        let mut request = reqwest::Request::new(method.clone(), url.clone());
+        if method == Method::POST {
+            *request.body_mut() = Some(form::encode(parameters).into());
+        } else {
+            let pairs = form::pairs(parameters);
+            if !pairs.is_empty() {
+                url.query_pairs_mut().extend_pairs(pairs);
+                *request.url_mut() = url.clone();
+            }
+        }
+
+        if !status.is_success() {
+            return Err(Error::from_context(ErrorContext {
+                status,
+                body: Some(body),
+                endpoint: url.path().to_owned(),
+                request_id: None,
+                attempts: 1,
+            }));
+        }
+        let headers = [("content-type".to_owned(), "application/json".to_owned())];
+        Ok(Response {
+            response: response_from_parts(&url, status.as_u16(), &headers, body.to_string())
+                .expect("fake response from valid parts"),
+            request,
+            attempts: 1,
+            cache_hit: false,
+        })
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("fake client lock poisoned")
+    }
+}
+
+/// Whether `pattern`, e.g. `podcasts/*` or `DELETE podcasts/*`, matches a call of `endpoint`.
+fn matches(pattern: &str, method: &Method, endpoint: &str) -> bool {
+    let pattern = match pattern.split_once(' ') {
+        Some((pattern_method, pattern)) if pattern_method.eq_ignore_ascii_case(method.as_str()) => pattern,
+        Some(_) => return false,
+        None => pattern,
+    };
+    let pattern = pattern.trim_matches('/');
+    pattern.split('/').count() == endpoint.split('/').count()
+        && pattern
+            .split('/')
+            .zip(endpoint.split('/'))
+            .all(|(pattern, segment)| pattern == "*" || pattern == segment)
+}
+
+impl PodcastApi for FakeClient {
+    fn search(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, "search", parameters)
+    }
+
+    fn search_episode_titles(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, "search_episode_titles", parameters)
+    }
+
+    fn typeahead(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, "typeahead", parameters)
+    }
+
+    fn spellcheck(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, "spellcheck", parameters)
+    }
+
+    fn fetch_related_searches(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, "related_searches", parameters)
+    }
+
+    fn fetch_trending_searches(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, "trending_searches", parameters)
+    }
+
+    fn fetch_best_podcasts(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, "best_podcasts", parameters)
+    }
+
+    fn fetch_podcast_by_id(&self, id: &str, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, &format!("podcasts/{}", id), parameters)
+    }
+
+    fn batch_fetch_podcasts(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::POST, "podcasts", parameters)
+    }
+
+    fn fetch_episode_by_id(&self, id: &str, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, &format!("episodes/{}", id), parameters)
+    }
+
+    fn batch_fetch_episodes(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::POST, "episodes", parameters)
+    }
+
+    fn fetch_curated_podcasts_list_by_id(
+        &self,
+        id: &str,
+        parameters: &Value,
+    ) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, &format!("curated_podcasts/{}", id), parameters)
+    }
+
+    fn fetch_curated_podcasts_lists(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, "curated_podcasts", parameters)
+    }
+
+    fn fetch_podcast_genres(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, "genres", parameters)
+    }
+
+    fn fetch_podcast_regions(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, "regions", parameters)
+    }
+
+    fn fetch_podcast_languages(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, "languages", parameters)
+    }
+
+    fn just_listen(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, "just_listen", parameters)
+    }
+
+    fn fetch_recommendations_for_podcast(
+        &self,
+        id: &str,
+        parameters: &Value,
+    ) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, &format!("podcasts/{}/recommendations", id), parameters)
+    }
+
+    fn fetch_recommendations_for_episode(
+        &self,
+        id: &str,
+        parameters: &Value,
+    ) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, &format!("episodes/{}/recommendations", id), parameters)
+    }
+
+    fn fetch_playlist_by_id(&self, id: &str, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, &format!("playlists/{}", id), parameters)
+    }
+
+    fn fetch_my_playlists(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, "playlists", parameters)
+    }
+
+    fn submit_podcast(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::POST, "podcasts/submit", parameters)
+    }
+
+    fn delete_podcast(&self, id: &str, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::DELETE, &format!("podcasts/{}", id), parameters)
+    }
+
+    fn fetch_audience_for_podcast(
+        &self,
+        id: &str,
+        parameters: &Value,
+    ) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, &format!("podcasts/{}/audience", id), parameters)
+    }
+
+    fn fetch_podcasts_by_domain(
+        &self,
+        domain_name: &str,
+        parameters: &Value,
+    ) -> impl Future<Output = Result<Response>> + Send {
+        self.call(Method::GET, &format!("podcasts/domains/{}", domain_name), parameters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoint_patterns() {
+        assert!(matches("search", &Method::GET, "search"));
+        assert!(matches("/podcasts/*", &Method::GET, "podcasts/abc"));
+        assert!(!matches("podcasts/*", &Method::GET, "podcasts/abc/audience"));
+        assert!(matches("delete podcasts/*", &Method::DELETE, "podcasts/abc"));
+        assert!(!matches("DELETE podcasts/*", &Method::GET, "podcasts/abc"));
+        assert!(!matches("podcasts", &Method::POST, "podcasts/submit"));
+    }
+
+    #[test]
+    fn programmed_responses() {
+        futures::executor::block_on(async {
+            let fake = FakeClient::new();
+            fake.respond("podcasts/*", json!({ "title": "first" }));
+            fake.respond("podcasts/*", json!({ "title": "second" }));
+            fake.respond("DELETE podcasts/*", json!({ "status": "in review" }));
+            fake.respond_once("podcasts/*", StatusCode::NOT_FOUND, json!({}));
+            fake.respond_once("podcasts/*", StatusCode::OK, json!({ "title": "once" }));
+
+            let parameters = json!({ "sort": "recent_first" });
+            let podcast = |id| fake.fetch_podcast_by_id(id, &parameters);
+            assert!(matches!(podcast("a").await, Err(Error::NotFoundError(_))));
+            let response = podcast("b").await.unwrap();
+            assert_eq!(response.request.url().query(), Some("sort=recent_first"));
+            assert_eq!(response.json().await.unwrap()["title"], "once");
+            assert_eq!(podcast("c").await.unwrap().json().await.unwrap()["title"], "second");
+
+            let response = fake.delete_podcast("d", &json!({})).await.unwrap();
+            assert_eq!(response.json().await.unwrap()["status"], "in review");
+
+            match fake.search(&json!({ "q": "startup" })).await {
+                Err(Error::NotFoundError(context)) => {
+                    assert_eq!(context.message(), Some("No fake response for GET search"));
+                    assert_eq!(context.endpoint, "/api/v2/search");
+                }
+                response => panic!("unexpected response {:?}", response),
+            }
+        });
+    }
+
+    #[test]
+    fn recorded_calls() {
+        futures::executor::block_on(async {
+            let fake = FakeClient::new();
+            fake.respond("podcasts/submit", json!({ "status": "in review" }));
+            let response = fake
+                .clone()
+                .submit_podcast(&json!({ "rss": "https://example.com/rss?a=1&b=2" }))
+                .await;
+            assert_eq!(
+                response.unwrap().request.body().and_then(|body| body.as_bytes()),
+                Some(&b"rss=https%3A%2F%2Fexample.com%2Frss%3Fa%3D1%26b%3D2"[..])
+            );
+            assert_eq!(
+                fake.calls(),
+                vec![FakeCall {
+                    method: Method::POST,
+                    endpoint: "podcasts/submit".to_owned(),
+                    parameters: json!({ "rss": "https://example.com/rss?a=1&b=2" }),
+                }]
+            );
+
+            let parameters = json!({ "q": "startup" });
+            let search = fake.search(&parameters);
+            assert_eq!(fake.calls().len(), 1);
+            assert!(search.await.is_err());
+            assert_eq!(fake.calls().len(), 2);
+        });
+    }
+}
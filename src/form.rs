@@ -0,0 +1,127 @@
+//! Encoding of JSON parameters as `application/x-www-form-urlencoded` bodies and query strings.
+//!
+//! Each value of the parameters object becomes one `key=value` pair:
+//! - strings are sent as they are and numbers in decimal,
+//! - booleans as `1` or `0`, like Listen API flags,
+//! - arrays as their elements joined with commas, like Listen API lists, e.g. `ids`,
+//! - nested objects as JSON,
+//! - `null` values are left out.
+use serde_json::Value;
+
+/// Pairs of `parameters`, which are empty unless it is an object.
+pub(crate) fn pairs(parameters: &Value) -> Vec<(String, String)> {
+    match parameters.as_object() {
+        Some(parameters) => parameters
+            .iter()
+            .filter(|(_, value)| !value.is_null())
+            .map(|(key, value)| (key.to_owned(), encode_value(value)))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Percent-encoded body of `parameters`.
+pub(crate) fn encode(parameters: &Value) -> String {
+    form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(pairs(parameters))
+        .finish()
+}
+
+fn encode_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Bool(value) => (*value as u8).to_string(),
+        Value::Number(value) => value.to_string(),
+        Value::String(value) => value.to_owned(),
+        Value::Array(values) => values
+            .iter()
+            .filter(|value| !value.is_null())
+            .map(|value| match value {
+                Value::Array(_) => value.to_string(),
+                _ => encode_value(value),
+            })
+            .collect::<Vec<_>>()
+            .join(","),
+        Value::Object(_) => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn decode(body: &str) -> Vec<(String, String)> {
+        form_urlencoded::parse(body.as_bytes()).into_owned().collect()
+    }
+
+    #[test]
+    fn policy() {
+        let parameters = json!({
+            "rss": "https://example.com/feed?a=1&b=2",
+            "q": "star wars",
+            "ids": ["a", "b", "c"],
+            "genre_ids": [68, 82],
+            "sort_by_date": true,
+            "safe_mode": false,
+            "offset": 10,
+            "published_after": null,
+            "filter": { "a": 1 }
+        });
+        assert_eq!(
+            decode(&encode(&parameters)),
+            vec![
+                ("filter".to_owned(), "{\"a\":1}".to_owned()),
+                ("genre_ids".to_owned(), "68,82".to_owned()),
+                ("ids".to_owned(), "a,b,c".to_owned()),
+                ("offset".to_owned(), "10".to_owned()),
+                ("q".to_owned(), "star wars".to_owned()),
+                ("rss".to_owned(), "https://example.com/feed?a=1&b=2".to_owned()),
+                ("safe_mode".to_owned(), "0".to_owned()),
+                ("sort_by_date".to_owned(), "1".to_owned()),
+            ]
+        );
+        assert_eq!(
+            encode(&json!({ "rss": "https://example.com/feed?a=1&b=2", "q": "star wars" })),
+            "q=star+wars&rss=https%3A%2F%2Fexample.com%2Ffeed%3Fa%3D1%26b%3D2"
+        );
+        assert_eq!(encode(&json!(["not", "an", "object"])), "");
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+        use std::collections::BTreeMap;
+
+        proptest! {
+            #[test]
+            fn strings_round_trip(parameters in prop::collection::btree_map(".*", ".*", 0..8)) {
+                let body = encode(&json!(parameters));
+                let decoded: BTreeMap<String, String> = decode(&body).into_iter().collect();
+                prop_assert_eq!(decoded, parameters);
+            }
+
+            #[test]
+            fn lists_round_trip(key in "[a-z_]{1,12}", ids in prop::collection::vec("[^,]+", 1..12)) {
+                let body = encode(&json!({ key.clone(): ids.clone() }));
+                let decoded = decode(&body);
+                prop_assert_eq!(decoded.len(), 1);
+                prop_assert_eq!(&decoded[0].0, &key);
+                prop_assert_eq!(decoded[0].1.split(',').collect::<Vec<_>>(), ids);
+            }
+
+            #[test]
+            fn scalars_round_trip(number in any::<i64>(), flag in any::<bool>()) {
+                let decoded = decode(&encode(&json!({ "number": number, "flag": flag, "skipped": null })));
+                prop_assert_eq!(
+                    decoded,
+                    vec![
+                        ("flag".to_owned(), (flag as u8).to_string()),
+                        ("number".to_owned(), number.to_string()),
+                    ]
+                );
+            }
+        }
+    }
+}
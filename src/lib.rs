@@ -35,14 +35,61 @@
 //! ```
 #![deny(missing_docs)]
 
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+compile_error!("the `wasm` feature is required for wasm32 targets");
+
 mod api;
+mod batch;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod builder;
+#[cfg(not(target_arch = "wasm32"))]
+mod cache;
+#[cfg(not(target_arch = "wasm32"))]
+mod cassette;
 mod client;
+#[cfg(not(target_arch = "wasm32"))]
+mod disk_store;
 mod error;
+#[cfg(not(target_arch = "wasm32"))]
+mod fake;
+mod form;
+#[cfg(feature = "mock-server")]
+mod mock;
+pub mod model;
+pub mod opml;
+pub mod params;
+pub mod playlist;
+#[cfg(not(target_arch = "wasm32"))]
+mod podcast_api;
+mod rate_limit;
+mod retry;
+pub mod rss;
+mod stream;
+mod usage;
 
 use api::Api;
 
+pub use batch::BatchResults;
+pub use builder::ClientBuilder;
+#[cfg(not(target_arch = "wasm32"))]
+pub use cache::{CacheStore, CachedResponse, MemoryStore, ResponseCache};
+#[cfg(not(target_arch = "wasm32"))]
+pub use cassette::Cassette;
 pub use client::Client;
 pub use client::Response;
-pub use error::Error;
+#[cfg(not(target_arch = "wasm32"))]
+pub use disk_store::DiskStore;
+pub use error::{Error, ErrorContext};
+#[cfg(not(target_arch = "wasm32"))]
+pub use fake::{FakeCall, FakeClient};
+#[cfg(feature = "mock-server")]
+pub use mock::MockServer;
+#[cfg(not(target_arch = "wasm32"))]
+pub use podcast_api::PodcastApi;
+pub use rate_limit::RateLimiter;
+pub use retry::RetryPolicy;
+pub use stream::{EpisodeCursor, EpisodeStream, SearchStream};
+pub use usage::Usage;
 /// Result for API calls from [`Client`]
 pub type Result<T> = std::result::Result<T, error::Error>;
@@ -0,0 +1,349 @@
+use super::Client;
+use http::{header, HeaderMap, Method, StatusCode};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
+use serde_json::json;
+use std::collections::{BTreeMap, VecDeque};
+use std::convert::Infallible;
+use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use tokio::sync::oneshot;
+
+/// In-process stand-in for the Listen API, bound to localhost.
+///
+/// Serves the example responses of the [API Docs](https://www.listennotes.com/api/docs/) for every
+/// endpoint of [`Client`], after checking required and unknown parameters like the real API does.
+/// Runs on its own thread, so it works with any async runtime and with blocking tests.
+/// ```
+/// # tokio_test::block_on(async {
+/// let server = podcast_api::MockServer::start();
+/// let client = server.client();
+///
+/// let genres = client.fetch_podcast_genres(&serde_json::json!({})).await.unwrap();
+/// assert!(!genres.genres().await.unwrap().genres.is_empty());
+///
+/// server.fail_next(http::StatusCode::TOO_MANY_REQUESTS);
+/// let response = client.fetch_podcast_genres(&serde_json::json!({})).await;
+/// assert!(matches!(response, Err(podcast_api::Error::RateLimitError(_))));
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    shutdown: Option<oneshot::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    api_key: Option<String>,
+    failures: VecDeque<StatusCode>,
+    requests: u64,
+    last_headers: Option<HeaderMap>,
+}
+
+impl MockServer {
+    /// Starts server on a free port of `127.0.0.1`.
+    ///
+    /// The server stops when dropped.
+    pub fn start() -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("MockServer::start()");
+        listener.set_nonblocking(true).expect("MockServer::start()");
+        let addr = listener.local_addr().expect("MockServer::start()");
+        let state = Arc::new(Mutex::new(State::default()));
+        let (shutdown, stopped) = oneshot::channel::<()>();
+
+        let service_state = state.clone();
+        let thread = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("MockServer::start()");
+            runtime.block_on(async move {
+                let make_service = make_service_fn(move |_| {
+                    let state = service_state.clone();
+                    async move {
+                        Ok::<_, Infallible>(service_fn(move |request| {
+                            let state = state.clone();
+                            async move { Ok::<_, Infallible>(handle(&state, request).await) }
+                        }))
+                    }
+                });
+                let server = Server::from_tcp(listener)
+                    .expect("MockServer::start()")
+                    .serve(make_service)
+                    .with_graceful_shutdown(async {
+                        stopped.await.ok();
+                    });
+                server.await.ok();
+            });
+        });
+
+        MockServer {
+            addr,
+            state,
+            shutdown: Some(shutdown),
+            thread: Some(thread),
+        }
+    }
+
+    /// Base URL of the API, e.g. `http://127.0.0.1:8080/api/v2`.
+    pub fn url(&self) -> String {
+        format!("http://{}/api/v2", self.addr)
+    }
+
+    /// Creates client calling this server.
+    pub fn client(&self) -> Client {
+        Client::builder()
+            .base_url(self.url())
+            .build()
+            .expect("MockServer::client()")
+    }
+
+    /// Responds with 401 to requests without the `X-ListenAPI-Key` header set to `api_key`.
+    ///
+    /// By default, any api key is accepted, like the mock API does.
+    pub fn require_api_key(&self, api_key: &str) {
+        self.state().api_key = Some(api_key.to_owned());
+    }
+
+    /// Responds to the next request with `status`, e.g. 404 or 429, instead of its example response.
+    ///
+    /// Repeated calls queue failures for consecutive requests.
+    pub fn fail_next(&self, status: StatusCode) {
+        self.state().failures.push_back(status);
+    }
+
+    /// Number of requests received so far.
+    pub fn requests(&self) -> u64 {
+        self.state().requests
+    }
+
+    /// Headers of the latest request received, if any.
+    pub fn last_request_headers(&self) -> Option<HeaderMap> {
+        self.state().last_headers.clone()
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("mock server lock poisoned")
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
+
+/// Endpoint served by [`MockServer`].
+struct Route {
+    method: Method,
+    /// Path below `/api/v2`, where `*` matches any single segment.
+    path: &'static str,
+    /// Groups of parameters, of which at least one per group is required.
+    required: &'static [&'static [&'static str]],
+    /// All parameters accepted, including required ones.
+    allowed: &'static [&'static str],
+    fixture: &'static str,
+}
+
+const SEARCH_PARAMETERS: &[&str] = &[
+    "q",
+    "sort_by_date",
+    "type",
+    "offset",
+    "len_min",
+    "len_max",
+    "episode_count_min",
+    "episode_count_max",
+    "update_freq_min",
+    "update_freq_max",
+    "genre_ids",
+    "published_before",
+    "published_after",
+    "only_in",
+    "language",
+    "region",
+    "ocid",
+    "ncid",
+    "safe_mode",
+    "unique_podcasts",
+    "interviews_only",
+    "sponsored_only",
+    "page_size",
+];
+
+#[rustfmt::skip]
+const ROUTES: &[Route] = &[
+    Route { method: Method::GET, path: "search", required: &[&["q"]], allowed: SEARCH_PARAMETERS, fixture: include_str!("../fixtures/search.json") },
+    Route { method: Method::GET, path: "search_episode_titles", required: &[&["q"]], allowed: &["q", "podcast_id"], fixture: include_str!("../fixtures/search_episode_titles.json") },
+    Route { method: Method::GET, path: "typeahead", required: &[&["q"]], allowed: &["q", "show_podcasts", "show_genres", "safe_mode"], fixture: include_str!("../fixtures/typeahead.json") },
+    Route { method: Method::GET, path: "spellcheck", required: &[&["q"]], allowed: &["q"], fixture: include_str!("../fixtures/spellcheck.json") },
+    Route { method: Method::GET, path: "related_searches", required: &[&["q"]], allowed: &["q"], fixture: include_str!("../fixtures/related_searches.json") },
+    Route { method: Method::GET, path: "trending_searches", required: &[], allowed: &[], fixture: include_str!("../fixtures/trending_searches.json") },
+    Route { method: Method::GET, path: "best_podcasts", required: &[], allowed: &["genre_id", "page", "region", "publisher_region", "language", "sort", "safe_mode"], fixture: include_str!("../fixtures/best_podcasts.json") },
+    Route { method: Method::POST, path: "podcasts/submit", required: &[&["rss"]], allowed: &["rss", "email"], fixture: include_str!("../fixtures/submit_podcast.json") },
+    Route { method: Method::GET, path: "podcasts/domains/*", required: &[], allowed: &["page"], fixture: include_str!("../fixtures/domain_podcasts.json") },
+    Route { method: Method::GET, path: "podcasts/*/recommendations", required: &[], allowed: &["safe_mode"], fixture: include_str!("../fixtures/podcast_recommendations.json") },
+    Route { method: Method::GET, path: "podcasts/*/audience", required: &[], allowed: &[], fixture: include_str!("../fixtures/audience.json") },
+    Route { method: Method::GET, path: "podcasts/*", required: &[], allowed: &["next_episode_pub_date", "sort"], fixture: include_str!("../fixtures/podcast.json") },
+    Route { method: Method::DELETE, path: "podcasts/*", required: &[], allowed: &["reason"], fixture: include_str!("../fixtures/delete_podcast.json") },
+    Route { method: Method::POST, path: "podcasts", required: &[&["ids", "rsses", "itunes_ids", "spotify_ids"]], allowed: &["ids", "rsses", "itunes_ids", "spotify_ids", "show_latest_episodes", "next_episode_pub_date"], fixture: include_str!("../fixtures/batch_podcasts.json") },
+    Route { method: Method::GET, path: "episodes/*/recommendations", required: &[], allowed: &["safe_mode"], fixture: include_str!("../fixtures/episode_recommendations.json") },
+    Route { method: Method::GET, path: "episodes/*", required: &[], allowed: &["show_transcript"], fixture: include_str!("../fixtures/episode.json") },
+    Route { method: Method::POST, path: "episodes", required: &[&["ids"]], allowed: &["ids"], fixture: include_str!("../fixtures/batch_episodes.json") },
+    Route { method: Method::GET, path: "curated_podcasts/*", required: &[], allowed: &[], fixture: include_str!("../fixtures/curated_podcast.json") },
+    Route { method: Method::GET, path: "curated_podcasts", required: &[], allowed: &["page"], fixture: include_str!("../fixtures/curated_podcasts.json") },
+    Route { method: Method::GET, path: "genres", required: &[], allowed: &["top_level_only"], fixture: include_str!("../fixtures/genres.json") },
+    Route { method: Method::GET, path: "regions", required: &[], allowed: &[], fixture: include_str!("../fixtures/regions.json") },
+    Route { method: Method::GET, path: "languages", required: &[], allowed: &[], fixture: include_str!("../fixtures/languages.json") },
+    Route { method: Method::GET, path: "just_listen", required: &[], allowed: &["genre_ids", "safe_mode"], fixture: include_str!("../fixtures/just_listen.json") },
+    Route { method: Method::GET, path: "playlists/*", required: &[], allowed: &["type", "last_timestamp_ms", "sort"], fixture: include_str!("../fixtures/playlist.json") },
+    Route { method: Method::GET, path: "playlists", required: &[], allowed: &["page", "sort"], fixture: include_str!("../fixtures/playlists.json") },
+];
+
+impl Route {
+    fn matches(&self, method: &Method, path: &str) -> bool {
+        let pattern = self.path.split('/');
+        let path = path.split('/');
+        self.method == method
+            && pattern.clone().count() == path.clone().count()
+            && pattern
+                .zip(path)
+                .all(|(pattern, segment)| !segment.is_empty() && (pattern == "*" || pattern == segment))
+    }
+
+    /// Error message for parameters the API would reject.
+    fn validate(&self, parameters: &BTreeMap<String, String>) -> Option<String> {
+        if let Some(unknown) = parameters.keys().find(|key| !self.allowed.contains(&key.as_str())) {
+            return Some(format!("Unknown parameter: {}", unknown));
+        }
+        self.required
+            .iter()
+            .find(|group| {
+                !group
+                    .iter()
+                    .any(|name| matches!(parameters.get(*name), Some(value) if !value.is_empty()))
+            })
+            .map(|group| format!("Missing required parameter: {}", group.join(" or ")))
+    }
+}
+
+async fn handle(state: &Mutex<State>, request: Request<Body>) -> Response<Body> {
+    let (request_id, failure, api_key) = {
+        let mut state = state.lock().expect("mock server lock poisoned");
+        state.requests += 1;
+        state.last_headers = Some(request.headers().clone());
+        (state.requests, state.failures.pop_front(), state.api_key.clone())
+    };
+
+    let (parts, body) = request.into_parts();
+    let body = hyper::body::to_bytes(body).await.unwrap_or_default();
+    let query = parts.uri.query().unwrap_or_default().as_bytes();
+    let parameters: BTreeMap<String, String> = form_urlencoded::parse(query)
+        .chain(form_urlencoded::parse(&body))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+
+    let path = parts.uri.path().strip_prefix("/api/v2/");
+    let route = path.and_then(|path| ROUTES.iter().find(|route| route.matches(&parts.method, path)));
+    let key = parts.headers.get("x-listenapi-key").and_then(|key| key.to_str().ok());
+
+    let (status, body) = if let Some(status) = failure {
+        (status, error_body(status, "Injected by MockServer"))
+    } else if api_key.is_some() && key != api_key.as_deref() {
+        (
+            StatusCode::UNAUTHORIZED,
+            error_body(StatusCode::UNAUTHORIZED, "Wrong api key"),
+        )
+    } else if let Some(route) = route {
+        match route.validate(&parameters) {
+            Some(message) => (StatusCode::BAD_REQUEST, error_body(StatusCode::BAD_REQUEST, &message)),
+            None => (StatusCode::OK, route.fixture.to_owned()),
+        }
+    } else {
+        (
+            StatusCode::NOT_FOUND,
+            error_body(StatusCode::NOT_FOUND, "Endpoint not exist"),
+        )
+    };
+
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("x-listenapi-freequota", "300")
+        .header("x-listenapi-usage", request_id.to_string())
+        .header("x-listenapi-latency-seconds", "0.001")
+        .header("x-request-id", format!("mock-{}", request_id))
+        .body(Body::from(body))
+        .expect("valid mock response")
+}
+
+fn error_body(status: StatusCode, message: &str) -> String {
+    json!({ "status": status.as_u16(), "message": message }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn fixtures() {
+        for route in ROUTES {
+            assert!(
+                serde_json::from_str::<Value>(route.fixture).is_ok(),
+                "{} {}",
+                route.method,
+                route.path
+            );
+        }
+    }
+
+    #[test]
+    fn route() {
+        let route = |method: Method, path: &str| {
+            ROUTES
+                .iter()
+                .find(|route| route.matches(&method, path))
+                .map(|route| route.path)
+        };
+        assert_eq!(route(Method::GET, "podcasts/abc"), Some("podcasts/*"));
+        assert_eq!(route(Method::GET, "podcasts/abc/audience"), Some("podcasts/*/audience"));
+        assert_eq!(
+            route(Method::GET, "podcasts/domains/nytimes.com"),
+            Some("podcasts/domains/*")
+        );
+        assert_eq!(route(Method::POST, "podcasts/submit"), Some("podcasts/submit"));
+        assert_eq!(route(Method::DELETE, "podcasts/abc"), Some("podcasts/*"));
+        assert_eq!(route(Method::GET, "podcasts/"), None);
+        assert_eq!(route(Method::GET, "unknown"), None);
+    }
+
+    #[test]
+    fn validate() {
+        let search = &ROUTES[0];
+        let parameters = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect()
+        };
+        assert_eq!(search.validate(&parameters(&[("q", "startup")])), None);
+        assert_eq!(
+            search.validate(&parameters(&[("sort_by_date", "1")])),
+            Some("Missing required parameter: q".to_owned())
+        );
+        assert_eq!(
+            search.validate(&parameters(&[("q", "startup"), ("foo", "1")])),
+            Some("Unknown parameter: foo".to_owned())
+        );
+    }
+}
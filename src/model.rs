@@ -0,0 +1,869 @@
+//! Typed response models for the [Listen API](https://www.listennotes.com/api/docs/).
+//!
+//! Each model mirrors the JSON schema of an endpoint's response body and can be obtained from a
+//! [`Response`](super::Response) through its typed accessors, e.g. [`Response::podcast`](super::Response::podcast).
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Podcast meta data, as returned by `GET /podcasts/{id}`, `GET /best_podcasts` and similar endpoints.
+///
+/// Secondary fields, e.g. [`extra`](Podcast::extra), are empty when the API omits them or returns `null`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Podcast {
+    /// Podcast id.
+    pub id: String,
+    /// Podcast title.
+    pub title: String,
+    /// Podcast publisher name.
+    pub publisher: Option<String>,
+    /// Html of this podcast's description.
+    pub description: String,
+    /// Image url for this podcast's artwork.
+    pub image: String,
+    /// Thumbnail image url for this podcast's artwork (300x300).
+    pub thumbnail: String,
+    /// RSS url of this podcast.
+    pub rss: String,
+    /// The type of this podcast, either `episodic` or `serial`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// The email of this podcast's producer.
+    pub email: Option<String>,
+    /// Website url of this podcast.
+    pub website: Option<String>,
+    /// The country where this podcast is produced.
+    pub country: Option<String>,
+    /// The language of this podcast.
+    pub language: String,
+    /// Genre ids.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub genre_ids: Vec<u32>,
+    /// iTunes id for this podcast.
+    pub itunes_id: Option<u64>,
+    /// Whether this podcast is claimed by its producer on ListenNotes.com.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub is_claimed: bool,
+    /// Whether this podcast contains explicit language.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub explicit_content: bool,
+    /// Links to this podcast on other platforms.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub extra: PodcastExtra,
+    /// What this podcast is looking for.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub looking_for: LookingFor,
+    /// The estimated popularity score of this podcast, if available.
+    pub listen_score: Option<u32>,
+    /// The estimated popularity ranking of this podcast, if available, e.g. `0.5%`.
+    pub listen_score_global_rank: Option<String>,
+    /// Total number of episodes in this podcast.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub total_episodes: u64,
+    /// Average audio length of all episodes of this podcast, in seconds.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub audio_length_sec: u64,
+    /// How frequently does this podcast release a new episode, in hours.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub update_frequency_hours: u64,
+    /// The id of the most recently published episode of this podcast.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub latest_episode_id: String,
+    /// The published date of the latest episode of this podcast, in milliseconds.
+    pub latest_pub_date_ms: u64,
+    /// The published date of the oldest episode of this podcast, in milliseconds.
+    pub earliest_pub_date_ms: u64,
+    /// The url of this podcast on ListenNotes.com.
+    pub listennotes_url: String,
+    /// Episodes of this podcast, only returned by `GET /podcasts/{id}`.
+    #[serde(default)]
+    pub episodes: Vec<Episode>,
+    /// Pass to `next_episode_pub_date` to fetch the next page of episodes, only returned by `GET /podcasts/{id}`.
+    #[serde(default)]
+    pub next_episode_pub_date: Option<u64>,
+}
+
+/// Links to a podcast on other platforms.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PodcastExtra {
+    /// Custom url.
+    pub url1: String,
+    /// Custom url.
+    pub url2: String,
+    /// Custom url.
+    pub url3: String,
+    /// Google Podcasts url.
+    pub google_url: String,
+    /// Spotify url.
+    pub spotify_url: String,
+    /// YouTube url.
+    pub youtube_url: String,
+    /// LinkedIn url.
+    pub linkedin_url: String,
+    /// WeChat handle.
+    pub wechat_handle: String,
+    /// Patreon handle.
+    pub patreon_handle: String,
+    /// Twitter handle.
+    pub twitter_handle: String,
+    /// Facebook handle.
+    pub facebook_handle: String,
+    /// Amazon Music url.
+    pub amazon_music_url: String,
+    /// Instagram handle.
+    pub instagram_handle: String,
+}
+
+/// What a podcast is looking for.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LookingFor {
+    /// Looking for guests.
+    pub guests: bool,
+    /// Looking for cohosts.
+    pub cohosts: bool,
+    /// Looking for sponsors.
+    pub sponsors: bool,
+    /// Looking for cross promotion.
+    pub cross_promotion: bool,
+}
+
+/// Compact podcast meta data, as embedded in episodes, curated lists and submissions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PodcastSummary {
+    /// Podcast id.
+    pub id: String,
+    /// Podcast title.
+    pub title: String,
+    /// Podcast publisher name.
+    pub publisher: Option<String>,
+    /// Image url for this podcast's artwork.
+    pub image: String,
+    /// Thumbnail image url for this podcast's artwork (300x300).
+    pub thumbnail: String,
+    /// The estimated popularity score of this podcast, if available.
+    pub listen_score: Option<u32>,
+    /// The estimated popularity ranking of this podcast, if available, e.g. `0.5%`.
+    pub listen_score_global_rank: Option<String>,
+    /// The url of this podcast on ListenNotes.com.
+    pub listennotes_url: String,
+}
+
+/// Episode meta data, as returned by `GET /episodes/{id}`, `POST /episodes` and similar endpoints.
+///
+/// `P` is the type of the embedded podcast, which is a full [`Podcast`] for `GET /episodes/{id}`
+/// and a [`PodcastSummary`] everywhere else.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Episode<P = PodcastSummary> {
+    /// Episode id.
+    pub id: String,
+    /// Episode title.
+    pub title: String,
+    /// Html of this episode's full description.
+    pub description: String,
+    /// Audio url of this episode.
+    pub audio: String,
+    /// Audio length of this episode, in seconds.
+    pub audio_length_sec: u64,
+    /// Image url for this episode.
+    pub image: String,
+    /// Thumbnail image url for this episode (300x300).
+    pub thumbnail: String,
+    /// Web link of this episode.
+    pub link: Option<String>,
+    /// Published date of this episode, in milliseconds.
+    pub pub_date_ms: u64,
+    /// Guid of this episode from the RSS feed.
+    #[serde(default)]
+    pub guid_from_rss: Option<String>,
+    /// Whether this episode contains explicit language.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub explicit_content: bool,
+    /// Whether this episode's audio is invalid.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub maybe_audio_invalid: bool,
+    /// The url of this episode on ListenNotes.com.
+    pub listennotes_url: String,
+    /// Edit url of this episode on ListenNotes.com.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub listennotes_edit_url: String,
+    /// Audio transcript, only returned by `GET /episodes/{id}` with `show_transcript=1`.
+    #[serde(default)]
+    pub transcript: Option<String>,
+    /// The podcast this episode belongs to, omitted for episodes embedded in a podcast.
+    #[serde(default = "Option::default")]
+    pub podcast: Option<P>,
+}
+
+/// Response of `GET /search` and `GET /search_episode_titles`.
+///
+/// `T` is the type of a single result, which depends on the `type` parameter of the search:
+/// [`SearchEpisode`] (default), [`SearchPodcast`] or [`SearchCuratedList`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchResults<T = SearchEpisode> {
+    /// The time it took to fetch these search results, in seconds.
+    pub took: f64,
+    /// The number of search results in this page.
+    pub count: u64,
+    /// The total number of search results.
+    pub total: u64,
+    /// Search results.
+    pub results: Vec<T>,
+    /// Pass to `offset` to fetch the next page of search results.
+    pub next_offset: u64,
+}
+
+/// Episode search result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchEpisode {
+    /// Episode id.
+    pub id: String,
+    /// Plain text of episode title.
+    pub title_original: String,
+    /// Highlighted segment of episode title.
+    pub title_highlighted: String,
+    /// Plain text of this episode's description.
+    pub description_original: String,
+    /// Highlighted segment of this episode's description.
+    pub description_highlighted: String,
+    /// Highlighted segments of transcripts.
+    #[serde(default)]
+    pub transcripts_highlighted: Vec<String>,
+    /// Audio url of this episode.
+    pub audio: String,
+    /// Audio length of this episode, in seconds.
+    pub audio_length_sec: u64,
+    /// RSS url of the podcast.
+    pub rss: String,
+    /// Web link of this episode.
+    pub link: String,
+    /// Image url for this episode.
+    pub image: String,
+    /// Thumbnail image url for this episode (300x300).
+    pub thumbnail: String,
+    /// iTunes id of the podcast.
+    pub itunes_id: Option<u64>,
+    /// Published date of this episode, in milliseconds.
+    pub pub_date_ms: u64,
+    /// Guid of this episode from the RSS feed.
+    #[serde(default)]
+    pub guid_from_rss: Option<String>,
+    /// Whether this episode contains explicit language.
+    pub explicit_content: bool,
+    /// The url of this episode on ListenNotes.com.
+    pub listennotes_url: String,
+    /// The podcast this episode belongs to.
+    pub podcast: SearchEpisodePodcast,
+}
+
+/// Podcast meta data embedded in an episode search result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchEpisodePodcast {
+    /// Podcast id.
+    pub id: String,
+    /// Plain text of podcast title.
+    pub title_original: String,
+    /// Highlighted segment of podcast title.
+    pub title_highlighted: String,
+    /// Plain text of podcast publisher name.
+    pub publisher_original: String,
+    /// Highlighted segment of podcast publisher name.
+    pub publisher_highlighted: String,
+    /// Image url for this podcast's artwork.
+    pub image: String,
+    /// Thumbnail image url for this podcast's artwork (300x300).
+    pub thumbnail: String,
+    /// Genre ids.
+    pub genre_ids: Vec<u32>,
+    /// The estimated popularity score of this podcast, if available.
+    pub listen_score: Option<u32>,
+    /// The estimated popularity ranking of this podcast, if available, e.g. `0.5%`.
+    pub listen_score_global_rank: Option<String>,
+    /// The url of this podcast on ListenNotes.com.
+    pub listennotes_url: String,
+}
+
+/// Podcast search result, returned when searching with `type=podcast`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchPodcast {
+    /// Podcast id.
+    pub id: String,
+    /// Plain text of podcast title.
+    pub title_original: String,
+    /// Highlighted segment of podcast title.
+    pub title_highlighted: String,
+    /// Plain text of podcast publisher name.
+    pub publisher_original: String,
+    /// Highlighted segment of podcast publisher name.
+    pub publisher_highlighted: String,
+    /// Plain text of podcast description.
+    pub description_original: String,
+    /// Highlighted segment of podcast description.
+    pub description_highlighted: String,
+    /// Image url for this podcast's artwork.
+    pub image: String,
+    /// Thumbnail image url for this podcast's artwork (300x300).
+    pub thumbnail: String,
+    /// RSS url of this podcast.
+    pub rss: String,
+    /// The email of this podcast's producer.
+    #[serde(default)]
+    pub email: Option<String>,
+    /// Website url of this podcast.
+    #[serde(default)]
+    pub website: Option<String>,
+    /// Genre ids.
+    pub genre_ids: Vec<u32>,
+    /// iTunes id for this podcast.
+    pub itunes_id: Option<u64>,
+    /// Whether this podcast contains explicit language.
+    pub explicit_content: bool,
+    /// The estimated popularity score of this podcast, if available.
+    pub listen_score: Option<u32>,
+    /// The estimated popularity ranking of this podcast, if available, e.g. `0.5%`.
+    pub listen_score_global_rank: Option<String>,
+    /// Total number of episodes in this podcast.
+    pub total_episodes: u64,
+    /// Average audio length of all episodes of this podcast, in seconds.
+    #[serde(default)]
+    pub audio_length_sec: Option<u64>,
+    /// How frequently does this podcast release a new episode, in hours.
+    #[serde(default)]
+    pub update_frequency_hours: Option<u64>,
+    /// The id of the most recently published episode of this podcast.
+    pub latest_episode_id: String,
+    /// The published date of the latest episode of this podcast, in milliseconds.
+    pub latest_pub_date_ms: u64,
+    /// The published date of the oldest episode of this podcast, in milliseconds.
+    pub earliest_pub_date_ms: u64,
+    /// The url of this podcast on ListenNotes.com.
+    pub listennotes_url: String,
+}
+
+/// Curated list search result, returned when searching with `type=curated`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchCuratedList {
+    /// Curated list id.
+    pub id: String,
+    /// Plain text of this curated list's title.
+    pub title_original: String,
+    /// Highlighted segment of this curated list's title.
+    pub title_highlighted: String,
+    /// Plain text of this curated list's description.
+    pub description_original: String,
+    /// Highlighted segment of this curated list's description.
+    pub description_highlighted: String,
+    /// Total number of podcasts in this curated list.
+    pub total: u64,
+    /// Podcasts in this curated list.
+    pub podcasts: Vec<PodcastSummary>,
+    /// Url of the source of this curated list.
+    pub source_url: String,
+    /// Domain name of the source of this curated list.
+    pub source_domain: String,
+    /// Published date of this curated list, in milliseconds.
+    pub pub_date_ms: u64,
+    /// The url of this curated list on ListenNotes.com.
+    pub listennotes_url: String,
+}
+
+/// Response of `GET /typeahead`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeaheadResults {
+    /// Search term suggestions.
+    pub terms: Vec<String>,
+    /// Genre suggestions, only returned with `show_genres=1`.
+    #[serde(default)]
+    pub genres: Vec<Genre>,
+    /// Podcast suggestions, only returned with `show_podcasts=1`.
+    #[serde(default)]
+    pub podcasts: Vec<TypeaheadPodcast>,
+}
+
+/// Podcast suggestion from `GET /typeahead`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeaheadPodcast {
+    /// Podcast id.
+    pub id: String,
+    /// Plain text of podcast title.
+    pub title_original: String,
+    /// Highlighted segment of podcast title.
+    pub title_highlighted: String,
+    /// Plain text of podcast publisher name.
+    pub publisher_original: String,
+    /// Highlighted segment of podcast publisher name.
+    pub publisher_highlighted: String,
+    /// Image url for this podcast's artwork.
+    pub image: String,
+    /// Thumbnail image url for this podcast's artwork (300x300).
+    pub thumbnail: String,
+    /// Whether this podcast contains explicit language.
+    pub explicit_content: bool,
+}
+
+/// Podcast genre.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Genre {
+    /// Genre id.
+    pub id: u32,
+    /// Genre name.
+    pub name: String,
+    /// Parent genre id, if any.
+    #[serde(default)]
+    pub parent_id: Option<u32>,
+}
+
+/// Response of `GET /genres`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Genres {
+    /// Podcast genres.
+    pub genres: Vec<Genre>,
+}
+
+/// Response of `GET /regions`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Regions {
+    /// Region names keyed by region code.
+    pub regions: BTreeMap<String, String>,
+}
+
+/// Response of `GET /languages`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Languages {
+    /// Language names.
+    pub languages: Vec<String>,
+}
+
+/// Response of `GET /best_podcasts`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BestPodcasts {
+    /// Genre id of these best podcasts.
+    pub id: u32,
+    /// Genre name of these best podcasts.
+    pub name: String,
+    /// Parent genre id, if any.
+    #[serde(default)]
+    pub parent_id: Option<u32>,
+    /// Best podcasts in this page.
+    pub podcasts: Vec<Podcast>,
+    /// Total number of best podcasts.
+    pub total: u64,
+    /// Whether there is a next page.
+    pub has_next: bool,
+    /// Whether there is a previous page.
+    pub has_previous: bool,
+    /// Current page number.
+    pub page_number: u64,
+    /// Next page number.
+    pub next_page_number: u64,
+    /// Previous page number.
+    pub previous_page_number: u64,
+    /// The url of these best podcasts on ListenNotes.com.
+    pub listennotes_url: String,
+}
+
+/// Response of `GET /podcasts/{id}/recommendations`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PodcastRecommendations {
+    /// Recommended podcasts.
+    pub recommendations: Vec<Podcast>,
+}
+
+/// Response of `GET /episodes/{id}/recommendations`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpisodeRecommendations {
+    /// Recommended episodes.
+    pub recommendations: Vec<Episode>,
+}
+
+/// Response of `POST /episodes`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchEpisodes {
+    /// Fetched episodes.
+    pub episodes: Vec<Episode>,
+}
+
+/// Response of `POST /podcasts`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchPodcasts {
+    /// Fetched podcasts.
+    pub podcasts: Vec<Podcast>,
+    /// Latest episodes of the fetched podcasts, only returned with `show_latest_episodes=1`.
+    #[serde(default)]
+    pub latest_episodes: Vec<Episode>,
+    /// Pass to `next_episode_pub_date` to fetch the next page of latest episodes.
+    #[serde(default)]
+    pub next_episode_pub_date: Option<u64>,
+}
+
+/// Curated list of podcasts.
+///
+/// `P` is the type of the podcasts in this list, which is a full [`Podcast`] for
+/// `GET /curated_podcasts/{id}` and a [`PodcastSummary`] for `GET /curated_podcasts`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CuratedList<P = PodcastSummary> {
+    /// Curated list id.
+    pub id: String,
+    /// Curated list title.
+    pub title: String,
+    /// Curated list description.
+    pub description: String,
+    /// Total number of podcasts in this curated list.
+    pub total: u64,
+    /// Podcasts in this curated list.
+    pub podcasts: Vec<P>,
+    /// Url of the source of this curated list.
+    pub source_url: String,
+    /// Domain name of the source of this curated list.
+    pub source_domain: String,
+    /// Published date of this curated list, in milliseconds.
+    pub pub_date_ms: u64,
+    /// The url of this curated list on ListenNotes.com.
+    pub listennotes_url: String,
+}
+
+/// Response of `GET /curated_podcasts`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CuratedLists {
+    /// Curated lists in this page.
+    pub curated_lists: Vec<CuratedList>,
+    /// Total number of curated lists.
+    pub total: u64,
+    /// Whether there is a next page.
+    pub has_next: bool,
+    /// Whether there is a previous page.
+    pub has_previous: bool,
+    /// Current page number.
+    pub page_number: u64,
+    /// Next page number.
+    pub next_page_number: u64,
+    /// Previous page number.
+    pub previous_page_number: u64,
+}
+
+/// Response of `POST /podcasts/submit`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PodcastSubmission {
+    /// The status of this submission, one of `found`, `in review` or `rejected`.
+    pub status: String,
+    /// The submitted podcast.
+    pub podcast: PodcastSummary,
+}
+
+/// Response of `DELETE /podcasts/{id}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PodcastDeletion {
+    /// The status of this deletion request, either `deleted` or `in review`.
+    pub status: String,
+}
+
+/// Response of `GET /playlists/{id}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Playlist {
+    /// Playlist id.
+    pub id: String,
+    /// Playlist name.
+    pub name: String,
+    /// Playlist description.
+    pub description: String,
+    /// The type of this playlist, either `episode_list` or `podcast_list`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// The visibility of this playlist, one of `public`, `unlisted` or `private`.
+    pub visibility: String,
+    /// Image url for this playlist.
+    pub image: String,
+    /// Thumbnail image url for this playlist (300x300).
+    pub thumbnail: String,
+    /// Items in this page.
+    pub items: Vec<PlaylistItem>,
+    /// Total number of items in this playlist.
+    pub total: u64,
+    /// Total audio length of all episodes in this playlist, in seconds.
+    pub total_audio_length_sec: u64,
+    /// Pass to `last_timestamp_ms` to fetch the next page of items.
+    pub last_timestamp_ms: u64,
+    /// The url of this playlist on ListenNotes.com.
+    pub listennotes_url: String,
+}
+
+/// Item of a [`Playlist`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlaylistItem {
+    /// Playlist item id.
+    pub id: u64,
+    /// The type of this item, one of `episode`, `custom_audio` or `podcast`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Notes for this item.
+    pub notes: String,
+    /// The time this item was added to the playlist, in milliseconds.
+    pub added_at_ms: u64,
+    /// Item data.
+    pub data: PlaylistItemData,
+}
+
+/// Data of a [`PlaylistItem`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PlaylistItemData {
+    /// An episode or podcast that has been deleted from the podcast database.
+    Deleted(DeletedItem),
+    /// A podcast.
+    Podcast(Box<Podcast>),
+    /// An episode.
+    Episode(Box<Episode>),
+    /// A custom audio.
+    CustomAudio(CustomAudio),
+}
+
+/// Episode or podcast that has been deleted from the podcast database.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeletedItem {
+    /// Episode or podcast id.
+    pub id: String,
+    /// Episode or podcast title.
+    pub title: String,
+    /// Reason for the deletion.
+    pub error: String,
+    /// Deletion status.
+    pub status: String,
+}
+
+/// Custom audio in a playlist.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomAudio {
+    /// Custom audio title.
+    pub title: String,
+    /// Audio url.
+    pub audio: String,
+    /// Audio length, in seconds.
+    pub audio_length_sec: u64,
+    /// Image url.
+    pub image: String,
+    /// Thumbnail image url (300x300).
+    pub thumbnail: String,
+    /// Published date, in milliseconds.
+    pub pub_date_ms: u64,
+}
+
+/// Response of `GET /playlists`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Playlists {
+    /// Playlists in this page.
+    pub playlists: Vec<PlaylistSummary>,
+    /// Total number of playlists.
+    pub total: u64,
+    /// Whether there is a next page.
+    pub has_next: bool,
+    /// Whether there is a previous page.
+    pub has_previous: bool,
+    /// Current page number.
+    pub page_number: u64,
+    /// Next page number.
+    pub next_page_number: u64,
+    /// Previous page number.
+    pub previous_page_number: u64,
+}
+
+/// Playlist meta data from `GET /playlists`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlaylistSummary {
+    /// Playlist id.
+    pub id: String,
+    /// Playlist name.
+    pub name: String,
+    /// Playlist description.
+    pub description: String,
+    /// The visibility of this playlist, one of `public`, `unlisted` or `private`.
+    pub visibility: String,
+    /// Image url for this playlist.
+    pub image: String,
+    /// Thumbnail image url for this playlist (300x300).
+    pub thumbnail: String,
+    /// Number of episodes in this playlist.
+    pub episode_count: u64,
+    /// Number of podcasts in this playlist.
+    pub podcast_count: u64,
+    /// Total audio length of all episodes in this playlist, in seconds.
+    pub total_audio_length_sec: u64,
+    /// The url of this playlist on ListenNotes.com.
+    pub listennotes_url: String,
+}
+
+/// Response of `GET /trending_searches` and `GET /related_searches`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchTerms {
+    /// Search terms.
+    pub terms: Vec<String>,
+}
+
+/// Response of `GET /spellcheck`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Spellcheck {
+    /// Html of the corrected search term.
+    pub corrected_text_html: String,
+    /// Misspelled tokens and their suggestions.
+    pub tokens: Vec<SpellcheckToken>,
+}
+
+/// Misspelled token of a search term.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpellcheckToken {
+    /// Misspelled token.
+    pub token: String,
+    /// Offset of the token in the search term.
+    pub offset: u64,
+    /// Suggested correction.
+    pub suggestion: String,
+}
+
+/// Response of `GET /podcasts/{id}/audience`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Audience {
+    /// Audience breakdown by region.
+    pub by_regions: Vec<AudienceRegion>,
+}
+
+/// Share of a podcast's audience in one region.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudienceRegion {
+    /// Region name.
+    pub region: String,
+    /// Ratio of the audience in this region, e.g. `86.15%`.
+    pub ratio: String,
+}
+
+/// Response of `GET /podcasts/domains/{domain_name}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DomainPodcasts {
+    /// Podcasts in this page.
+    pub podcasts: Vec<Podcast>,
+    /// Whether there is a next page.
+    pub has_next: bool,
+    /// Whether there is a previous page.
+    pub has_previous: bool,
+    /// Current page number.
+    pub page_number: u64,
+    /// Next page number.
+    #[serde(default)]
+    pub next_page_number: Option<u64>,
+    /// Previous page number.
+    #[serde(default)]
+    pub previous_page_number: Option<u64>,
+}
+
+/// Deserializes `null` like a missing field, to the default value.
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn podcast_summary() -> serde_json::Value {
+        json!({
+            "id": "9a62e2581908415185dee35d2d19f9b5",
+            "image": "https://example.com/image.jpg",
+            "title": "This Week in Startups",
+            "publisher": "Jason Calacanis",
+            "thumbnail": "https://example.com/thumbnail.jpg",
+            "listen_score": null,
+            "listennotes_url": "https://www.listennotes.com/c/9a62e2581908415185dee35d2d19f9b5/",
+            "listen_score_global_rank": null
+        })
+    }
+
+    #[test]
+    fn episode_without_podcast() {
+        let episode: Episode = serde_json::from_value(json!({
+            "id": "4e7c59e10e4640b98f2f3cb1777dbb43",
+            "link": "https://example.com/864",
+            "audio": "https://www.listennotes.com/e/p/4e7c59e10e4640b98f2f3cb1777dbb43/",
+            "image": "https://example.com/image.jpg",
+            "title": "864: Part 2",
+            "thumbnail": "https://example.com/thumbnail.jpg",
+            "description": "<p>Punch it!</p>",
+            "pub_date_ms": 1479110402345u64,
+            "listennotes_url": "https://www.listennotes.com/e/4e7c59e10e4640b98f2f3cb1777dbb43/",
+            "audio_length_sec": 2447,
+            "explicit_content": false,
+            "maybe_audio_invalid": false,
+            "listennotes_edit_url": "https://www.listennotes.com/e/4e7c59e10e4640b98f2f3cb1777dbb43/#edit"
+        }))
+        .unwrap();
+        assert_eq!(episode.audio_length_sec, 2447);
+        assert!(episode.podcast.is_none());
+        assert!(episode.guid_from_rss.is_none());
+    }
+
+    #[test]
+    fn podcast_with_missing_fields() {
+        let mut podcast: serde_json::Value = serde_json::from_str(include_str!("../fixtures/podcast.json")).unwrap();
+        let fields = podcast.as_object_mut().unwrap();
+        fields.remove("extra");
+        fields.remove("looking_for");
+        fields.remove("is_claimed");
+        fields.insert("update_frequency_hours".to_owned(), json!(null));
+        fields.insert("latest_episode_id".to_owned(), json!(null));
+        fields.insert("publisher".to_owned(), json!(null));
+        let podcast: Podcast = serde_json::from_value(podcast).unwrap();
+        assert_eq!(podcast.extra, PodcastExtra::default());
+        assert!(!podcast.is_claimed);
+        assert_eq!(podcast.update_frequency_hours, 0);
+        assert_eq!(podcast.latest_episode_id, "");
+        assert_eq!(podcast.publisher, None);
+    }
+
+    #[test]
+    fn podcast_submission() {
+        let submission: PodcastSubmission = serde_json::from_value(json!({
+            "status": "found",
+            "podcast": podcast_summary()
+        }))
+        .unwrap();
+        assert_eq!(submission.status, "found");
+        assert_eq!(submission.podcast.listen_score, None);
+    }
+
+    #[test]
+    fn playlist_item_data() {
+        let deleted: PlaylistItem = serde_json::from_value(json!({
+            "id": 764336,
+            "data": {
+                "id": "aba7f2dd81a5408f96da8d70dfbefe36",
+                "error": "This episode has been deleted from the podcast database.",
+                "title": "Spotify's ad-tech acquisitions",
+                "status": "deleted"
+            },
+            "type": "episode",
+            "notes": "",
+            "added_at_ms": 1645211291773u64
+        }))
+        .unwrap();
+        assert!(matches!(deleted.data, PlaylistItemData::Deleted(_)));
+
+        let custom: PlaylistItem = serde_json::from_value(json!({
+            "id": 764337,
+            "data": {
+                "audio": "https://example.com/audio.mp3",
+                "image": "https://example.com/image.jpg",
+                "title": "Custom",
+                "thumbnail": "https://example.com/thumbnail.jpg",
+                "pub_date_ms": 1645211291773u64,
+                "audio_length_sec": 60
+            },
+            "type": "custom_audio",
+            "notes": "",
+            "added_at_ms": 1645211291773u64
+        }))
+        .unwrap();
+        assert!(matches!(custom.data, PlaylistItemData::CustomAudio(_)));
+    }
+}
@@ -0,0 +1,340 @@
+//! Importing podcast subscriptions from OPML files, and exporting podcasts to them.
+//!
+//! ```no_run
+//! # tokio_test::block_on(async {
+//! let client = podcast_api::Client::new(Some("YOUR-API-KEY"));
+//! let opml = std::fs::read_to_string("subscriptions.opml").unwrap();
+//!
+//! let import = client.import_opml(&opml, 4).await.unwrap();
+//! for feed in &import.unmatched {
+//!     println!("Not on Listen Notes: {}", feed.xml_url);
+//! }
+//!
+//! let ids: Vec<&str> = import.matched.iter().map(|(_, podcast)| podcast.id.as_str()).collect();
+//! let opml = client.export_opml("Subscriptions", &ids, 4).await.unwrap();
+//! # });
+//! ```
+use super::batch::MAX_BATCH_IDS;
+use super::params::{BatchPodcastsParams, SearchParams, SearchType};
+use super::{model, Client, Error, Result};
+use futures::{StreamExt, TryStreamExt};
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::{HashMap, HashSet};
+
+/// Feed of an OPML file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feed {
+    /// Title of the feed, from `title` or `text`.
+    pub title: Option<String>,
+    /// RSS url.
+    pub xml_url: String,
+    /// Website url.
+    pub html_url: Option<String>,
+    /// iTunes id, taken from Apple Podcasts urls.
+    pub itunes_id: Option<u64>,
+}
+
+/// Results of [`Client::import_opml`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Import {
+    /// Feeds with the podcast they resolved to, in the order of the OPML file.
+    pub matched: Vec<(Feed, model::Podcast)>,
+    /// Feeds which are not in the podcast database, or whose search failed, in the order of the OPML file.
+    pub unmatched: Vec<Feed>,
+}
+
+/// Feeds of `opml`, including those nested in folders, in their order.
+///
+/// Fails with [`Error::ValidationError`] if `opml` is not an OPML document.
+pub fn parse(opml: &str) -> Result<Vec<Feed>> {
+    let mut reader = Reader::from_str(opml);
+    let mut feeds = Vec::new();
+    let mut is_opml = false;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) | Ok(Event::Empty(element)) => match element.name().as_ref() {
+                b"opml" => is_opml = true,
+                b"outline" if is_opml => feeds.extend(feed(&element)?),
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(err) => return Err(invalid(err)),
+        }
+    }
+    if !is_opml {
+        return Err(Error::ValidationError("invalid OPML: missing opml element".to_owned()));
+    }
+    Ok(feeds)
+}
+
+/// OPML document titled `title`, with an outline for each of `podcasts`.
+pub fn write(title: &str, podcasts: &[model::Podcast]) -> String {
+    let mut opml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"2.0\">\n  <head>\n    <title>{}</title>\n  </head>\n  <body>\n",
+        escape(title)
+    );
+    for podcast in podcasts {
+        opml.push_str(&format!(
+            "    <outline type=\"rss\" text=\"{title}\" title=\"{title}\" xmlUrl=\"{}\"",
+            escape(podcast.rss.as_str()),
+            title = escape(podcast.title.trim()),
+        ));
+        if let Some(website) = podcast.website.as_deref().filter(|website| !website.is_empty()) {
+            opml.push_str(&format!(" htmlUrl=\"{}\"", escape(website)));
+        }
+        opml.push_str("/>\n");
+    }
+    opml.push_str("  </body>\n</opml>\n");
+    opml
+}
+
+impl Feed {
+    /// Whether this is the feed with `rss` or `itunes_id`.
+    fn is(&self, rss: &str, itunes_id: Option<u64>) -> bool {
+        same_url(rss, &self.xml_url) || (self.itunes_id.is_some() && itunes_id == self.itunes_id)
+    }
+}
+
+impl Client {
+    /// Resolves the feeds of `opml` to podcasts, running up to `concurrency` calls at once.
+    ///
+    /// Feeds are looked up by RSS url and iTunes id with [`Client::batch_fetch_podcasts`], then the remaining
+    /// ones with [`Client::search`], accepting only results with the same RSS url or iTunes id.
+    /// Feeds whose search fails are left unmatched, but the import fails if `opml` can't be parsed
+    /// or a batch call fails.
+    pub async fn import_opml(&self, opml: &str, concurrency: usize) -> Result<Import> {
+        let mut pending: Vec<(usize, Feed)> = parse(opml)?.into_iter().enumerate().collect();
+        let mut matched = Vec::new();
+
+        let rsses: Vec<&str> = unique(pending.iter().map(|(_, feed)| feed.xml_url.as_str()));
+        let podcasts = self
+            .batch_podcasts(&rsses, concurrency, |chunk| BatchPodcastsParams::new().rsses(chunk))
+            .await?;
+        pending = resolve(pending, podcasts, &mut matched);
+
+        let itunes_ids: Vec<u64> = unique(pending.iter().filter_map(|(_, feed)| feed.itunes_id));
+        let podcasts = self
+            .batch_podcasts(&itunes_ids, concurrency, |chunk| {
+                BatchPodcastsParams::new().itunes_ids(chunk)
+            })
+            .await?;
+        pending = resolve(pending, podcasts, &mut matched);
+
+        // A failed search, e.g. after retries ran out, only loses its feed rather than every match so far.
+        let ids: Vec<Result<Option<String>>> =
+            futures::stream::iter(pending.iter().map(|(_, feed)| self.search_feed(feed)))
+                .buffered(concurrency.max(1))
+                .collect()
+                .await;
+        let ids: Vec<&str> = unique(ids.iter().filter_map(|id| id.as_ref().ok()?.as_deref()));
+        let podcasts = self.fetch_podcasts_by_ids(&ids, concurrency).await?.found;
+        pending = resolve(pending, podcasts, &mut matched);
+
+        matched.sort_by_key(|(index, _, _)| *index);
+        Ok(Import {
+            matched: matched.into_iter().map(|(_, feed, podcast)| (feed, podcast)).collect(),
+            unmatched: pending.into_iter().map(|(_, feed)| feed).collect(),
+        })
+    }
+
+    /// OPML document titled `title` with the podcasts of `ids`, fetched with up to `concurrency` calls at once.
+    ///
+    /// Ids which are not in the podcast database are left out.
+    pub async fn export_opml(&self, title: &str, ids: &[&str], concurrency: usize) -> Result<String> {
+        let podcasts = self.fetch_podcasts_by_ids(ids, concurrency).await?.found;
+        Ok(write(title, &podcasts))
+    }
+
+    /// Podcasts of `POST /podcasts` calls with the parameters of `params` for each chunk of `values`.
+    async fn batch_podcasts<T>(
+        &self,
+        values: &[T],
+        concurrency: usize,
+        params: impl Fn(&[T]) -> BatchPodcastsParams,
+    ) -> Result<Vec<model::Podcast>> {
+        let params = &params;
+        let pages: Vec<Vec<model::Podcast>> =
+            futures::stream::iter(values.chunks(MAX_BATCH_IDS).map(|chunk| async move {
+                let parameters = params(chunk).build()?;
+                Ok::<_, Error>(
+                    self.batch_fetch_podcasts(&parameters)
+                        .await?
+                        .batch_podcasts()
+                        .await?
+                        .podcasts,
+                )
+            }))
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await?;
+        Ok(pages.into_iter().flatten().collect())
+    }
+
+    /// Id of the podcast search result with the RSS url or iTunes id of `feed`.
+    async fn search_feed(&self, feed: &Feed) -> Result<Option<String>> {
+        let parameters = SearchParams::new(&feed.xml_url).kind(SearchType::Podcast).build()?;
+        let results = self.search(&parameters).await?.json().await?;
+        Ok(results["results"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|result| feed.is(result["rss"].as_str().unwrap_or_default(), result["itunes_id"].as_u64()))
+            .and_then(|result| result["id"].as_str())
+            .map(str::to_owned))
+    }
+}
+
+fn feed(element: &BytesStart) -> Result<Option<Feed>> {
+    let mut attributes = HashMap::new();
+    for attribute in element.attributes() {
+        let attribute = attribute.map_err(invalid)?;
+        let value = attribute.unescape_value().map_err(invalid)?;
+        attributes.insert(attribute.key.as_ref().to_ascii_lowercase(), value.trim().to_owned());
+    }
+    let mut attribute = |name: &[u8]| attributes.remove(name).filter(|value| !value.is_empty());
+
+    // Outlines without feed are folders.
+    let xml_url = match attribute(b"xmlurl") {
+        Some(xml_url) => xml_url,
+        None => return Ok(None),
+    };
+    let html_url = attribute(b"htmlurl");
+    let itunes_id = itunes_id(&xml_url).or_else(|| html_url.as_deref().and_then(itunes_id));
+    Ok(Some(Feed {
+        title: attribute(b"title").or_else(|| attribute(b"text")),
+        xml_url,
+        html_url,
+        itunes_id,
+    }))
+}
+
+fn invalid(err: impl std::fmt::Display) -> Error {
+    Error::ValidationError(format!("invalid OPML: {}", err))
+}
+
+/// iTunes id of an Apple Podcasts url, e.g. `https://podcasts.apple.com/us/podcast/exponent/id826420969`.
+fn itunes_id(url: &str) -> Option<u64> {
+    let (host, path) = split_url(url);
+    if !(host.ends_with("podcasts.apple.com") || host.ends_with("itunes.apple.com")) {
+        return None;
+    }
+    path.split(['/', '?', '&'])
+        .find_map(|segment| segment.strip_prefix("id"))
+        .and_then(|id| id.parse().ok())
+}
+
+/// Whether urls are the same, ignoring the scheme, case of the host and trailing slashes.
+fn same_url(a: &str, b: &str) -> bool {
+    let ((a_host, a_path), (b_host, b_path)) = (split_url(a), split_url(b));
+    a_host.eq_ignore_ascii_case(b_host) && a_path.trim_end_matches('/') == b_path.trim_end_matches('/')
+}
+
+/// Host and path of `url`, without its scheme.
+fn split_url(url: &str) -> (&str, &str) {
+    let url = url.trim();
+    let url = url.split_once("://").map_or(url, |(_, url)| url);
+    url.split_at(url.find('/').unwrap_or(url.len()))
+}
+
+fn unique<T: Eq + std::hash::Hash + Copy>(values: impl Iterator<Item = T>) -> Vec<T> {
+    let mut seen = HashSet::new();
+    values.filter(|value| seen.insert(*value)).collect()
+}
+
+/// Moves the feeds of `pending` with one of `podcasts` to `matched`, and returns the others.
+fn resolve(
+    pending: Vec<(usize, Feed)>,
+    podcasts: Vec<model::Podcast>,
+    matched: &mut Vec<(usize, Feed, model::Podcast)>,
+) -> Vec<(usize, Feed)> {
+    pending
+        .into_iter()
+        .filter_map(|(index, feed)| {
+            let podcast = podcasts.iter().find(|podcast| feed.is(&podcast.rss, podcast.itunes_id));
+            match podcast {
+                Some(podcast) => {
+                    matched.push((index, feed, podcast.clone()));
+                    None
+                }
+                None => Some((index, feed)),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn feeds() {
+        let opml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <opml version="1.0">
+              <head><title>Subscriptions</title></head>
+              <body>
+                <outline text="Tech">
+                  <outline type="rss" text="Exponent" xmlUrl="https://exponent.fm/feed/" htmlUrl="https://exponent.fm"/>
+                  <outline TEXT="Binge &amp; Mode" XMLURL=" https://podcasts.apple.com/us/podcast/binge-mode/id1243247464 "/>
+                </outline>
+                <outline text="Empty" xmlUrl=""/>
+              </body>
+            </opml>"#;
+        assert_eq!(
+            parse(opml).unwrap(),
+            vec![
+                Feed {
+                    title: Some("Exponent".to_owned()),
+                    xml_url: "https://exponent.fm/feed/".to_owned(),
+                    html_url: Some("https://exponent.fm".to_owned()),
+                    itunes_id: None,
+                },
+                Feed {
+                    title: Some("Binge & Mode".to_owned()),
+                    xml_url: "https://podcasts.apple.com/us/podcast/binge-mode/id1243247464".to_owned(),
+                    html_url: None,
+                    itunes_id: Some(1243247464),
+                },
+            ]
+        );
+        assert!(matches!(parse("<rss></rss>"), Err(Error::ValidationError(_))));
+        assert!(matches!(
+            parse("<opml><body><outline xmlUrl=\"a></body>"),
+            Err(Error::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn urls() {
+        assert!(same_url("https://Exponent.fm/feed/", "http://exponent.fm/feed"));
+        assert!(!same_url("https://exponent.fm/feed", "https://exponent.fm/Feed"));
+        assert_eq!(
+            itunes_id("https://itunes.apple.com/us/podcast/id826420969?mt=2"),
+            Some(826420969)
+        );
+        assert_eq!(itunes_id("https://example.com/id826420969"), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let podcast: model::Podcast = serde_json::from_value(
+            serde_json::from_str::<Value>(include_str!("../fixtures/batch_podcasts.json")).unwrap()["podcasts"][0]
+                .clone(),
+        )
+        .unwrap();
+        let opml = write("Mine <3", std::slice::from_ref(&podcast));
+        assert!(opml.contains("<title>Mine &lt;3</title>"));
+        assert_eq!(
+            parse(&opml).unwrap(),
+            vec![Feed {
+                title: Some(podcast.title.clone()),
+                xml_url: podcast.rss.clone(),
+                html_url: podcast.website.clone(),
+                itunes_id: None,
+            }]
+        );
+    }
+}
@@ -0,0 +1,1149 @@
+//! Typed request parameters for the [Listen API](https://www.listennotes.com/api/docs/).
+//!
+//! Each builder validates its values client-side and produces the `&Value` parameters that [`Client`](super::Client)
+//! methods take, so mistakes are reported as [`Error::ValidationError`](super::Error::ValidationError) before any
+//! request is sent:
+//! ```
+//! use podcast_api::params::{OnlyIn, SearchParams};
+//!
+//! let parameters = SearchParams::new("startup")
+//!     .only_in(&[OnlyIn::Title, OnlyIn::Description])
+//!     .genre_ids(&[68, 82])
+//!     .published_after(1390190241000)
+//!     .build()
+//!     .unwrap();
+//! ```
+use super::{Error, Result};
+use serde_json::{Map, Value};
+use std::fmt::Display;
+
+/// Type of results returned by [`Client::search`](super::Client::search).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchType {
+    /// Search episodes.
+    Episode,
+    /// Search podcasts.
+    Podcast,
+    /// Search curated lists of podcasts.
+    Curated,
+}
+
+impl SearchType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SearchType::Episode => "episode",
+            SearchType::Podcast => "podcast",
+            SearchType::Curated => "curated",
+        }
+    }
+}
+
+/// Field to restrict a full-text search to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnlyIn {
+    /// Title of episodes, podcasts or curated lists.
+    Title,
+    /// Description of episodes, podcasts or curated lists.
+    Description,
+    /// Author (publisher) of podcasts.
+    Author,
+    /// Audio transcripts of episodes.
+    Audio,
+}
+
+impl OnlyIn {
+    fn as_str(&self) -> &'static str {
+        match self {
+            OnlyIn::Title => "title",
+            OnlyIn::Description => "description",
+            OnlyIn::Author => "author",
+            OnlyIn::Audio => "audio",
+        }
+    }
+}
+
+/// Sort order of episodes for [`Client::fetch_podcast_by_id`](super::Client::fetch_podcast_by_id).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EpisodeSort {
+    /// Newest episodes first.
+    RecentFirst,
+    /// Oldest episodes first.
+    OldestFirst,
+}
+
+impl EpisodeSort {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            EpisodeSort::RecentFirst => "recent_first",
+            EpisodeSort::OldestFirst => "oldest_first",
+        }
+    }
+}
+
+/// Sort order for [`Client::fetch_best_podcasts`](super::Client::fetch_best_podcasts).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BestPodcastsSort {
+    /// Recently added podcasts first.
+    RecentAddedFirst,
+    /// Oldest added podcasts first.
+    OldestAddedFirst,
+    /// Recently published podcasts first.
+    RecentPublishedFirst,
+    /// Oldest published podcasts first.
+    OldestPublishedFirst,
+    /// Highest Listen Score first.
+    ListenScore,
+}
+
+impl BestPodcastsSort {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BestPodcastsSort::RecentAddedFirst => "recent_added_first",
+            BestPodcastsSort::OldestAddedFirst => "oldest_added_first",
+            BestPodcastsSort::RecentPublishedFirst => "recent_published_first",
+            BestPodcastsSort::OldestPublishedFirst => "oldest_published_first",
+            BestPodcastsSort::ListenScore => "listen_score",
+        }
+    }
+}
+
+/// Type of items to fetch for [`Client::fetch_playlist_by_id`](super::Client::fetch_playlist_by_id).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaylistType {
+    /// Fetch the episodes of the playlist.
+    EpisodeList,
+    /// Fetch the podcasts of the playlist.
+    PodcastList,
+}
+
+impl PlaylistType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PlaylistType::EpisodeList => "episode_list",
+            PlaylistType::PodcastList => "podcast_list",
+        }
+    }
+}
+
+/// Sort order of items for [`Client::fetch_playlist_by_id`](super::Client::fetch_playlist_by_id).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaylistItemSort {
+    /// Recently added items first.
+    RecentAddedFirst,
+    /// Oldest added items first.
+    OldestAddedFirst,
+    /// Recently published items first.
+    RecentPublishedFirst,
+    /// Oldest published items first.
+    OldestPublishedFirst,
+}
+
+impl PlaylistItemSort {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PlaylistItemSort::RecentAddedFirst => "recent_added_first",
+            PlaylistItemSort::OldestAddedFirst => "oldest_added_first",
+            PlaylistItemSort::RecentPublishedFirst => "recent_published_first",
+            PlaylistItemSort::OldestPublishedFirst => "oldest_published_first",
+        }
+    }
+}
+
+/// Sort order of playlists for [`Client::fetch_my_playlists`](super::Client::fetch_my_playlists).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaylistSort {
+    /// Recently added playlists first.
+    RecentAddedFirst,
+    /// Oldest added playlists first.
+    OldestAddedFirst,
+    /// Playlist names in alphabetical order.
+    NameAToZ,
+    /// Playlist names in reverse alphabetical order.
+    NameZToA,
+}
+
+impl PlaylistSort {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PlaylistSort::RecentAddedFirst => "recent_added_first",
+            PlaylistSort::OldestAddedFirst => "oldest_added_first",
+            PlaylistSort::NameAToZ => "name_a_to_z",
+            PlaylistSort::NameZToA => "name_z_to_a",
+        }
+    }
+}
+
+/// Parameters for [`Client::search`](super::Client::search).
+#[derive(Debug, Clone, Default)]
+pub struct SearchParams {
+    q: String,
+    sort_by_date: Option<bool>,
+    kind: Option<SearchType>,
+    offset: Option<u32>,
+    len_min: Option<u32>,
+    len_max: Option<u32>,
+    episode_count_min: Option<u32>,
+    episode_count_max: Option<u32>,
+    update_freq_min: Option<u32>,
+    update_freq_max: Option<u32>,
+    genre_ids: Vec<u32>,
+    published_before: Option<u64>,
+    published_after: Option<u64>,
+    only_in: Vec<OnlyIn>,
+    language: Option<String>,
+    region: Option<String>,
+    ocid: Option<String>,
+    ncid: Option<String>,
+    safe_mode: Option<bool>,
+    unique_podcasts: Option<bool>,
+    interviews_only: Option<bool>,
+    sponsored_only: Option<bool>,
+    page_size: Option<u32>,
+}
+
+impl SearchParams {
+    /// Creates search parameters for search term `q`.
+    pub fn new(q: &str) -> SearchParams {
+        SearchParams {
+            q: q.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Sort by date, instead of relevance.
+    pub fn sort_by_date(mut self, sort_by_date: bool) -> Self {
+        self.sort_by_date = Some(sort_by_date);
+        self
+    }
+
+    /// Type of results to search for.
+    pub fn kind(mut self, kind: SearchType) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Offset for pagination, taken from `next_offset` of the previous page.
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Minimum audio length in minutes, for episode search only.
+    pub fn len_min(mut self, minutes: u32) -> Self {
+        self.len_min = Some(minutes);
+        self
+    }
+
+    /// Maximum audio length in minutes, for episode search only.
+    pub fn len_max(mut self, minutes: u32) -> Self {
+        self.len_max = Some(minutes);
+        self
+    }
+
+    /// Minimum number of episodes, for podcast search only.
+    pub fn episode_count_min(mut self, count: u32) -> Self {
+        self.episode_count_min = Some(count);
+        self
+    }
+
+    /// Maximum number of episodes, for podcast search only.
+    pub fn episode_count_max(mut self, count: u32) -> Self {
+        self.episode_count_max = Some(count);
+        self
+    }
+
+    /// Minimum update frequency in days, for podcast search only.
+    pub fn update_freq_min(mut self, days: u32) -> Self {
+        self.update_freq_min = Some(days);
+        self
+    }
+
+    /// Maximum update frequency in days, for podcast search only.
+    pub fn update_freq_max(mut self, days: u32) -> Self {
+        self.update_freq_max = Some(days);
+        self
+    }
+
+    /// Restrict results to these genres.
+    pub fn genre_ids(mut self, genre_ids: &[u32]) -> Self {
+        self.genre_ids = genre_ids.to_vec();
+        self
+    }
+
+    /// Only include results published before this time, in milliseconds.
+    pub fn published_before(mut self, ms: u64) -> Self {
+        self.published_before = Some(ms);
+        self
+    }
+
+    /// Only include results published after this time, in milliseconds.
+    pub fn published_after(mut self, ms: u64) -> Self {
+        self.published_after = Some(ms);
+        self
+    }
+
+    /// Only search in these fields.
+    pub fn only_in(mut self, only_in: &[OnlyIn]) -> Self {
+        self.only_in = only_in.to_vec();
+        self
+    }
+
+    /// Restrict results to this language, e.g. `English`.
+    pub fn language(mut self, language: &str) -> Self {
+        self.language = Some(language.to_owned());
+        self
+    }
+
+    /// Restrict results to this region code, e.g. `us`.
+    pub fn region(mut self, region: &str) -> Self {
+        self.region = Some(region.to_owned());
+        self
+    }
+
+    /// Only include episodes of these podcasts, comma separated podcast ids.
+    pub fn ocid(mut self, ocid: &str) -> Self {
+        self.ocid = Some(ocid.to_owned());
+        self
+    }
+
+    /// Exclude results from this podcast id.
+    pub fn ncid(mut self, ncid: &str) -> Self {
+        self.ncid = Some(ncid.to_owned());
+        self
+    }
+
+    /// Exclude podcasts with explicit language.
+    pub fn safe_mode(mut self, safe_mode: bool) -> Self {
+        self.safe_mode = Some(safe_mode);
+        self
+    }
+
+    /// Return at most one episode per podcast, for episode search only.
+    pub fn unique_podcasts(mut self, unique_podcasts: bool) -> Self {
+        self.unique_podcasts = Some(unique_podcasts);
+        self
+    }
+
+    /// Only include interviews, for episode search only.
+    pub fn interviews_only(mut self, interviews_only: bool) -> Self {
+        self.interviews_only = Some(interviews_only);
+        self
+    }
+
+    /// Only include sponsored results.
+    pub fn sponsored_only(mut self, sponsored_only: bool) -> Self {
+        self.sponsored_only = Some(sponsored_only);
+        self
+    }
+
+    /// Number of results per page, from 1 to 10.
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Validates parameters and builds them for [`Client::search`](super::Client::search).
+    pub fn build(&self) -> Result<Value> {
+        require("q", &self.q)?;
+        ordered("len_min", self.len_min, "len_max", self.len_max)?;
+        ordered("episode_count_min", self.episode_count_min, "episode_count_max", self.episode_count_max)?;
+        ordered("update_freq_min", self.update_freq_min, "update_freq_max", self.update_freq_max)?;
+        ordered("published_after", self.published_after, "published_before", self.published_before)?;
+        within("page_size", self.page_size, 1, 10)?;
+
+        let kind = self.kind.unwrap_or(SearchType::Episode);
+        if kind != SearchType::Episode {
+            exclusive("len_min", self.len_min.is_some(), "type", kind.as_str())?;
+            exclusive("len_max", self.len_max.is_some(), "type", kind.as_str())?;
+            exclusive("unique_podcasts", self.unique_podcasts.is_some(), "type", kind.as_str())?;
+            exclusive("interviews_only", self.interviews_only.is_some(), "type", kind.as_str())?;
+        }
+        if kind != SearchType::Podcast {
+            exclusive("episode_count_min", self.episode_count_min.is_some(), "type", kind.as_str())?;
+            exclusive("episode_count_max", self.episode_count_max.is_some(), "type", kind.as_str())?;
+            exclusive("update_freq_min", self.update_freq_min.is_some(), "type", kind.as_str())?;
+            exclusive("update_freq_max", self.update_freq_max.is_some(), "type", kind.as_str())?;
+        }
+
+        Ok(Parameters::default()
+            .set("q", Some(self.q.as_str()))
+            .flag("sort_by_date", self.sort_by_date)
+            .set("type", self.kind.map(|kind| kind.as_str()))
+            .set("offset", self.offset)
+            .set("len_min", self.len_min)
+            .set("len_max", self.len_max)
+            .set("episode_count_min", self.episode_count_min)
+            .set("episode_count_max", self.episode_count_max)
+            .set("update_freq_min", self.update_freq_min)
+            .set("update_freq_max", self.update_freq_max)
+            .list("genre_ids", &self.genre_ids)
+            .set("published_before", self.published_before)
+            .set("published_after", self.published_after)
+            .list("only_in", &self.only_in.iter().map(|only_in| only_in.as_str()).collect::<Vec<_>>())
+            .set("language", self.language.as_deref())
+            .set("region", self.region.as_deref())
+            .set("ocid", self.ocid.as_deref())
+            .set("ncid", self.ncid.as_deref())
+            .flag("safe_mode", self.safe_mode)
+            .flag("unique_podcasts", self.unique_podcasts)
+            .flag("interviews_only", self.interviews_only)
+            .flag("sponsored_only", self.sponsored_only)
+            .set("page_size", self.page_size)
+            .into())
+    }
+}
+
+/// Parameters for [`Client::search_episode_titles`](super::Client::search_episode_titles).
+#[derive(Debug, Clone, Default)]
+pub struct SearchEpisodeTitlesParams {
+    q: String,
+    podcast_id: Option<String>,
+}
+
+impl SearchEpisodeTitlesParams {
+    /// Creates episode title search parameters for search term `q`.
+    pub fn new(q: &str) -> SearchEpisodeTitlesParams {
+        SearchEpisodeTitlesParams {
+            q: q.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Only search episodes of this podcast.
+    pub fn podcast_id(mut self, podcast_id: &str) -> Self {
+        self.podcast_id = Some(podcast_id.to_owned());
+        self
+    }
+
+    /// Validates parameters and builds them for [`Client::search_episode_titles`](super::Client::search_episode_titles).
+    pub fn build(&self) -> Result<Value> {
+        require("q", &self.q)?;
+
+        Ok(Parameters::default()
+            .set("q", Some(self.q.as_str()))
+            .set("podcast_id", self.podcast_id.as_deref())
+            .into())
+    }
+}
+
+/// Parameters for [`Client::typeahead`](super::Client::typeahead).
+#[derive(Debug, Clone, Default)]
+pub struct TypeaheadParams {
+    q: String,
+    show_podcasts: Option<bool>,
+    show_genres: Option<bool>,
+    safe_mode: Option<bool>,
+}
+
+impl TypeaheadParams {
+    /// Creates typeahead parameters for search term `q`.
+    pub fn new(q: &str) -> TypeaheadParams {
+        TypeaheadParams {
+            q: q.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Also suggest podcasts.
+    pub fn show_podcasts(mut self, show_podcasts: bool) -> Self {
+        self.show_podcasts = Some(show_podcasts);
+        self
+    }
+
+    /// Also suggest genres.
+    pub fn show_genres(mut self, show_genres: bool) -> Self {
+        self.show_genres = Some(show_genres);
+        self
+    }
+
+    /// Exclude podcasts with explicit language.
+    pub fn safe_mode(mut self, safe_mode: bool) -> Self {
+        self.safe_mode = Some(safe_mode);
+        self
+    }
+
+    /// Validates parameters and builds them for [`Client::typeahead`](super::Client::typeahead).
+    pub fn build(&self) -> Result<Value> {
+        require("q", &self.q)?;
+
+        Ok(Parameters::default()
+            .set("q", Some(self.q.as_str()))
+            .flag("show_podcasts", self.show_podcasts)
+            .flag("show_genres", self.show_genres)
+            .flag("safe_mode", self.safe_mode)
+            .into())
+    }
+}
+
+/// Parameters for [`Client::spellcheck`](super::Client::spellcheck) and
+/// [`Client::fetch_related_searches`](super::Client::fetch_related_searches).
+#[derive(Debug, Clone, Default)]
+pub struct TermParams {
+    q: String,
+}
+
+impl TermParams {
+    /// Creates parameters for search term `q`.
+    pub fn new(q: &str) -> TermParams {
+        TermParams { q: q.to_owned() }
+    }
+
+    /// Validates parameters and builds them.
+    pub fn build(&self) -> Result<Value> {
+        require("q", &self.q)?;
+
+        Ok(Parameters::default().set("q", Some(self.q.as_str())).into())
+    }
+}
+
+/// Parameters for [`Client::fetch_best_podcasts`](super::Client::fetch_best_podcasts).
+#[derive(Debug, Clone, Default)]
+pub struct BestPodcastsParams {
+    genre_id: Option<u32>,
+    page: Option<u32>,
+    region: Option<String>,
+    publisher_region: Option<String>,
+    language: Option<String>,
+    sort: Option<BestPodcastsSort>,
+    safe_mode: Option<bool>,
+}
+
+impl BestPodcastsParams {
+    /// Creates empty best podcasts parameters.
+    pub fn new() -> BestPodcastsParams {
+        Default::default()
+    }
+
+    /// Genre of best podcasts.
+    pub fn genre_id(mut self, genre_id: u32) -> Self {
+        self.genre_id = Some(genre_id);
+        self
+    }
+
+    /// Page number, starting from 1.
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Region code of best podcasts, e.g. `us`.
+    pub fn region(mut self, region: &str) -> Self {
+        self.region = Some(region.to_owned());
+        self
+    }
+
+    /// Region code of podcast publishers, e.g. `us`.
+    pub fn publisher_region(mut self, publisher_region: &str) -> Self {
+        self.publisher_region = Some(publisher_region.to_owned());
+        self
+    }
+
+    /// Language of best podcasts, e.g. `English`.
+    pub fn language(mut self, language: &str) -> Self {
+        self.language = Some(language.to_owned());
+        self
+    }
+
+    /// Sort order.
+    pub fn sort(mut self, sort: BestPodcastsSort) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
+    /// Exclude podcasts with explicit language.
+    pub fn safe_mode(mut self, safe_mode: bool) -> Self {
+        self.safe_mode = Some(safe_mode);
+        self
+    }
+
+    /// Validates parameters and builds them for [`Client::fetch_best_podcasts`](super::Client::fetch_best_podcasts).
+    pub fn build(&self) -> Result<Value> {
+        within("page", self.page, 1, u32::MAX)?;
+
+        Ok(Parameters::default()
+            .set("genre_id", self.genre_id)
+            .set("page", self.page)
+            .set("region", self.region.as_deref())
+            .set("publisher_region", self.publisher_region.as_deref())
+            .set("language", self.language.as_deref())
+            .set("sort", self.sort.map(|sort| sort.as_str()))
+            .flag("safe_mode", self.safe_mode)
+            .into())
+    }
+}
+
+/// Parameters for [`Client::fetch_podcast_by_id`](super::Client::fetch_podcast_by_id).
+#[derive(Debug, Clone, Default)]
+pub struct PodcastParams {
+    next_episode_pub_date: Option<u64>,
+    sort: Option<EpisodeSort>,
+}
+
+impl PodcastParams {
+    /// Creates empty podcast parameters.
+    pub fn new() -> PodcastParams {
+        Default::default()
+    }
+
+    /// Fetch episodes from this time on, taken from `next_episode_pub_date` of the previous page.
+    pub fn next_episode_pub_date(mut self, ms: u64) -> Self {
+        self.next_episode_pub_date = Some(ms);
+        self
+    }
+
+    /// Sort order of episodes.
+    pub fn sort(mut self, sort: EpisodeSort) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
+    /// Validates parameters and builds them for [`Client::fetch_podcast_by_id`](super::Client::fetch_podcast_by_id).
+    pub fn build(&self) -> Result<Value> {
+        Ok(Parameters::default()
+            .set("next_episode_pub_date", self.next_episode_pub_date)
+            .set("sort", self.sort.map(|sort| sort.as_str()))
+            .into())
+    }
+}
+
+/// Parameters for [`Client::fetch_episode_by_id`](super::Client::fetch_episode_by_id).
+#[derive(Debug, Clone, Default)]
+pub struct EpisodeParams {
+    show_transcript: Option<bool>,
+}
+
+impl EpisodeParams {
+    /// Creates empty episode parameters.
+    pub fn new() -> EpisodeParams {
+        Default::default()
+    }
+
+    /// Include the audio transcript, if available.
+    pub fn show_transcript(mut self, show_transcript: bool) -> Self {
+        self.show_transcript = Some(show_transcript);
+        self
+    }
+
+    /// Validates parameters and builds them for [`Client::fetch_episode_by_id`](super::Client::fetch_episode_by_id).
+    pub fn build(&self) -> Result<Value> {
+        Ok(Parameters::default()
+            .flag("show_transcript", self.show_transcript)
+            .into())
+    }
+}
+
+/// Parameters for [`Client::batch_fetch_podcasts`](super::Client::batch_fetch_podcasts).
+#[derive(Debug, Clone, Default)]
+pub struct BatchPodcastsParams {
+    ids: Vec<String>,
+    rsses: Vec<String>,
+    itunes_ids: Vec<u64>,
+    spotify_ids: Vec<String>,
+    show_latest_episodes: Option<bool>,
+    next_episode_pub_date: Option<u64>,
+}
+
+impl BatchPodcastsParams {
+    /// Creates empty batch podcasts parameters.
+    pub fn new() -> BatchPodcastsParams {
+        Default::default()
+    }
+
+    /// Listen Notes podcast ids.
+    pub fn ids(mut self, ids: &[&str]) -> Self {
+        self.ids = ids.iter().map(|id| id.to_string()).collect();
+        self
+    }
+
+    /// RSS urls.
+    pub fn rsses(mut self, rsses: &[&str]) -> Self {
+        self.rsses = rsses.iter().map(|rss| rss.to_string()).collect();
+        self
+    }
+
+    /// iTunes ids.
+    pub fn itunes_ids(mut self, itunes_ids: &[u64]) -> Self {
+        self.itunes_ids = itunes_ids.to_vec();
+        self
+    }
+
+    /// Spotify ids.
+    pub fn spotify_ids(mut self, spotify_ids: &[&str]) -> Self {
+        self.spotify_ids = spotify_ids.iter().map(|id| id.to_string()).collect();
+        self
+    }
+
+    /// Also fetch the latest episodes of these podcasts.
+    pub fn show_latest_episodes(mut self, show_latest_episodes: bool) -> Self {
+        self.show_latest_episodes = Some(show_latest_episodes);
+        self
+    }
+
+    /// Fetch latest episodes from this time on, taken from `next_episode_pub_date` of the previous page.
+    pub fn next_episode_pub_date(mut self, ms: u64) -> Self {
+        self.next_episode_pub_date = Some(ms);
+        self
+    }
+
+    /// Validates parameters and builds them for [`Client::batch_fetch_podcasts`](super::Client::batch_fetch_podcasts).
+    pub fn build(&self) -> Result<Value> {
+        if self.ids.is_empty() && self.rsses.is_empty() && self.itunes_ids.is_empty() && self.spotify_ids.is_empty() {
+            return Err(Error::ValidationError(
+                "one of ids, rsses, itunes_ids or spotify_ids is required".to_owned(),
+            ));
+        }
+        if self.next_episode_pub_date.is_some() && self.show_latest_episodes != Some(true) {
+            return Err(Error::ValidationError(
+                "next_episode_pub_date requires show_latest_episodes".to_owned(),
+            ));
+        }
+
+        Ok(Parameters::default()
+            .list("ids", &self.ids)
+            .list("rsses", &self.rsses)
+            .list("itunes_ids", &self.itunes_ids)
+            .list("spotify_ids", &self.spotify_ids)
+            .flag("show_latest_episodes", self.show_latest_episodes)
+            .set("next_episode_pub_date", self.next_episode_pub_date)
+            .into())
+    }
+}
+
+/// Parameters for [`Client::batch_fetch_episodes`](super::Client::batch_fetch_episodes).
+#[derive(Debug, Clone, Default)]
+pub struct BatchEpisodesParams {
+    ids: Vec<String>,
+}
+
+impl BatchEpisodesParams {
+    /// Creates batch episodes parameters for these episode ids.
+    pub fn new(ids: &[&str]) -> BatchEpisodesParams {
+        BatchEpisodesParams {
+            ids: ids.iter().map(|id| id.to_string()).collect(),
+        }
+    }
+
+    /// Validates parameters and builds them for [`Client::batch_fetch_episodes`](super::Client::batch_fetch_episodes).
+    pub fn build(&self) -> Result<Value> {
+        if self.ids.is_empty() {
+            return Err(Error::ValidationError("ids is required".to_owned()));
+        }
+
+        Ok(Parameters::default().list("ids", &self.ids).into())
+    }
+}
+
+/// Parameters for [`Client::fetch_podcast_genres`](super::Client::fetch_podcast_genres).
+#[derive(Debug, Clone, Default)]
+pub struct GenresParams {
+    top_level_only: Option<bool>,
+}
+
+impl GenresParams {
+    /// Creates empty genres parameters.
+    pub fn new() -> GenresParams {
+        Default::default()
+    }
+
+    /// Only fetch top level genres.
+    pub fn top_level_only(mut self, top_level_only: bool) -> Self {
+        self.top_level_only = Some(top_level_only);
+        self
+    }
+
+    /// Validates parameters and builds them for [`Client::fetch_podcast_genres`](super::Client::fetch_podcast_genres).
+    pub fn build(&self) -> Result<Value> {
+        Ok(Parameters::default()
+            .flag("top_level_only", self.top_level_only)
+            .into())
+    }
+}
+
+/// Parameters for [`Client::just_listen`](super::Client::just_listen).
+#[derive(Debug, Clone, Default)]
+pub struct JustListenParams {
+    genre_ids: Vec<u32>,
+    safe_mode: Option<bool>,
+}
+
+impl JustListenParams {
+    /// Creates empty just listen parameters.
+    pub fn new() -> JustListenParams {
+        Default::default()
+    }
+
+    /// Restrict the random episode to these genres.
+    pub fn genre_ids(mut self, genre_ids: &[u32]) -> Self {
+        self.genre_ids = genre_ids.to_vec();
+        self
+    }
+
+    /// Exclude episodes with explicit language.
+    pub fn safe_mode(mut self, safe_mode: bool) -> Self {
+        self.safe_mode = Some(safe_mode);
+        self
+    }
+
+    /// Validates parameters and builds them for [`Client::just_listen`](super::Client::just_listen).
+    pub fn build(&self) -> Result<Value> {
+        Ok(Parameters::default()
+            .list("genre_ids", &self.genre_ids)
+            .flag("safe_mode", self.safe_mode)
+            .into())
+    }
+}
+
+/// Parameters for [`Client::fetch_recommendations_for_podcast`](super::Client::fetch_recommendations_for_podcast)
+/// and [`Client::fetch_recommendations_for_episode`](super::Client::fetch_recommendations_for_episode).
+#[derive(Debug, Clone, Default)]
+pub struct RecommendationsParams {
+    safe_mode: Option<bool>,
+}
+
+impl RecommendationsParams {
+    /// Creates empty recommendations parameters.
+    pub fn new() -> RecommendationsParams {
+        Default::default()
+    }
+
+    /// Exclude recommendations with explicit language.
+    pub fn safe_mode(mut self, safe_mode: bool) -> Self {
+        self.safe_mode = Some(safe_mode);
+        self
+    }
+
+    /// Validates parameters and builds them.
+    pub fn build(&self) -> Result<Value> {
+        Ok(Parameters::default().flag("safe_mode", self.safe_mode).into())
+    }
+}
+
+/// Parameters for [`Client::fetch_playlist_by_id`](super::Client::fetch_playlist_by_id).
+#[derive(Debug, Clone, Default)]
+pub struct PlaylistParams {
+    kind: Option<PlaylistType>,
+    last_timestamp_ms: Option<u64>,
+    sort: Option<PlaylistItemSort>,
+}
+
+impl PlaylistParams {
+    /// Creates empty playlist parameters.
+    pub fn new() -> PlaylistParams {
+        Default::default()
+    }
+
+    /// Type of items to fetch.
+    pub fn kind(mut self, kind: PlaylistType) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Fetch items from this time on, taken from `last_timestamp_ms` of the previous page.
+    pub fn last_timestamp_ms(mut self, ms: u64) -> Self {
+        self.last_timestamp_ms = Some(ms);
+        self
+    }
+
+    /// Sort order of items.
+    pub fn sort(mut self, sort: PlaylistItemSort) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
+    /// Validates parameters and builds them for [`Client::fetch_playlist_by_id`](super::Client::fetch_playlist_by_id).
+    pub fn build(&self) -> Result<Value> {
+        Ok(Parameters::default()
+            .set("type", self.kind.map(|kind| kind.as_str()))
+            .set("last_timestamp_ms", self.last_timestamp_ms)
+            .set("sort", self.sort.map(|sort| sort.as_str()))
+            .into())
+    }
+}
+
+/// Parameters for [`Client::fetch_my_playlists`](super::Client::fetch_my_playlists).
+#[derive(Debug, Clone, Default)]
+pub struct PlaylistsParams {
+    page: Option<u32>,
+    sort: Option<PlaylistSort>,
+}
+
+impl PlaylistsParams {
+    /// Creates empty playlists parameters.
+    pub fn new() -> PlaylistsParams {
+        Default::default()
+    }
+
+    /// Page number, starting from 1.
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Sort order of playlists.
+    pub fn sort(mut self, sort: PlaylistSort) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
+    /// Validates parameters and builds them for [`Client::fetch_my_playlists`](super::Client::fetch_my_playlists).
+    pub fn build(&self) -> Result<Value> {
+        within("page", self.page, 1, u32::MAX)?;
+
+        Ok(Parameters::default()
+            .set("page", self.page)
+            .set("sort", self.sort.map(|sort| sort.as_str()))
+            .into())
+    }
+}
+
+/// Parameters for [`Client::fetch_curated_podcasts_lists`](super::Client::fetch_curated_podcasts_lists) and
+/// [`Client::fetch_podcasts_by_domain`](super::Client::fetch_podcasts_by_domain).
+#[derive(Debug, Clone, Default)]
+pub struct PageParams {
+    page: Option<u32>,
+}
+
+impl PageParams {
+    /// Creates empty page parameters.
+    pub fn new() -> PageParams {
+        Default::default()
+    }
+
+    /// Page number, starting from 1.
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Validates parameters and builds them.
+    pub fn build(&self) -> Result<Value> {
+        within("page", self.page, 1, u32::MAX)?;
+
+        Ok(Parameters::default().set("page", self.page).into())
+    }
+}
+
+/// Parameters for [`Client::submit_podcast`](super::Client::submit_podcast).
+#[derive(Debug, Clone, Default)]
+pub struct SubmitPodcastParams {
+    rss: String,
+    email: Option<String>,
+}
+
+impl SubmitPodcastParams {
+    /// Creates submission parameters for this RSS url.
+    pub fn new(rss: &str) -> SubmitPodcastParams {
+        SubmitPodcastParams {
+            rss: rss.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Email to notify once the podcast is accepted.
+    pub fn email(mut self, email: &str) -> Self {
+        self.email = Some(email.to_owned());
+        self
+    }
+
+    /// Validates parameters and builds them for [`Client::submit_podcast`](super::Client::submit_podcast).
+    pub fn build(&self) -> Result<Value> {
+        require("rss", &self.rss)?;
+        if !self.rss.starts_with("http://") && !self.rss.starts_with("https://") {
+            return Err(Error::ValidationError(format!("rss must be an http(s) url: {}", self.rss)));
+        }
+        if let Some(email) = &self.email {
+            if !email.contains('@') {
+                return Err(Error::ValidationError(format!("email is not valid: {}", email)));
+            }
+        }
+
+        Ok(Parameters::default()
+            .set("rss", Some(self.rss.as_str()))
+            .set("email", self.email.as_deref())
+            .into())
+    }
+}
+
+/// Parameters for [`Client::delete_podcast`](super::Client::delete_podcast).
+#[derive(Debug, Clone, Default)]
+pub struct DeletePodcastParams {
+    reason: Option<String>,
+}
+
+impl DeletePodcastParams {
+    /// Creates empty deletion parameters.
+    pub fn new() -> DeletePodcastParams {
+        Default::default()
+    }
+
+    /// Reason for the deletion.
+    pub fn reason(mut self, reason: &str) -> Self {
+        self.reason = Some(reason.to_owned());
+        self
+    }
+
+    /// Validates parameters and builds them for [`Client::delete_podcast`](super::Client::delete_podcast).
+    pub fn build(&self) -> Result<Value> {
+        Ok(Parameters::default().set("reason", self.reason.as_deref()).into())
+    }
+}
+
+/// JSON object of parameters, skipping unset values.
+#[derive(Default)]
+struct Parameters(Map<String, Value>);
+
+impl Parameters {
+    fn set<T: Into<Value>>(mut self, key: &str, value: Option<T>) -> Self {
+        if let Some(value) = value {
+            self.0.insert(key.to_owned(), value.into());
+        }
+        self
+    }
+
+    /// Listen API flags are `0` or `1`.
+    fn flag(self, key: &str, value: Option<bool>) -> Self {
+        self.set(key, value.map(|value| value as u8))
+    }
+
+    /// Listen API lists are comma separated.
+    fn list<T: Display>(self, key: &str, values: &[T]) -> Self {
+        let value = if values.is_empty() {
+            None
+        } else {
+            Some(values.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(","))
+        };
+        self.set(key, value)
+    }
+}
+
+impl From<Parameters> for Value {
+    fn from(parameters: Parameters) -> Value {
+        Value::Object(parameters.0)
+    }
+}
+
+fn require(key: &str, value: &str) -> Result<()> {
+    if value.trim().is_empty() {
+        return Err(Error::ValidationError(format!("{} is required", key)));
+    }
+    Ok(())
+}
+
+fn ordered<T: PartialOrd + Display>(min_key: &str, min: Option<T>, max_key: &str, max: Option<T>) -> Result<()> {
+    if let (Some(min), Some(max)) = (min, max) {
+        if min > max {
+            return Err(Error::ValidationError(format!(
+                "{} ({}) must not be greater than {} ({})",
+                min_key, min, max_key, max
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn within(key: &str, value: Option<u32>, min: u32, max: u32) -> Result<()> {
+    if let Some(value) = value {
+        if value < min || value > max {
+            return Err(Error::ValidationError(format!(
+                "{} ({}) must be between {} and {}",
+                key, value, min, max
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn exclusive(key: &str, is_set: bool, other_key: &str, other_value: &str) -> Result<()> {
+    if is_set {
+        return Err(Error::ValidationError(format!(
+            "{} cannot be used with {}={}",
+            key, other_key, other_value
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn search() {
+        assert_eq!(
+            SearchParams::new("startup")
+                .sort_by_date(true)
+                .only_in(&[OnlyIn::Title, OnlyIn::Description])
+                .genre_ids(&[68, 82])
+                .published_after(1390190241000)
+                .build()
+                .unwrap(),
+            json!({
+                "q": "startup",
+                "sort_by_date": 1,
+                "only_in": "title,description",
+                "genre_ids": "68,82",
+                "published_after": 1390190241000u64
+            })
+        );
+    }
+
+    #[test]
+    fn search_validation() {
+        assert!(matches!(SearchParams::new(" ").build(), Err(Error::ValidationError(_))));
+        assert!(matches!(
+            SearchParams::new("q").len_min(10).len_max(5).build(),
+            Err(Error::ValidationError(_))
+        ));
+        assert!(matches!(
+            SearchParams::new("q").page_size(11).build(),
+            Err(Error::ValidationError(_))
+        ));
+        assert!(matches!(
+            SearchParams::new("q").kind(SearchType::Podcast).len_min(5).build(),
+            Err(Error::ValidationError(_))
+        ));
+        assert!(matches!(
+            SearchParams::new("q").episode_count_min(5).build(),
+            Err(Error::ValidationError(_))
+        ));
+        assert!(SearchParams::new("q")
+            .kind(SearchType::Podcast)
+            .episode_count_min(5)
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn batch_fetch_podcasts() {
+        assert!(matches!(
+            BatchPodcastsParams::new().build(),
+            Err(Error::ValidationError(_))
+        ));
+        assert_eq!(
+            BatchPodcastsParams::new()
+                .ids(&["996", "777"])
+                .itunes_ids(&[333])
+                .show_latest_episodes(true)
+                .build()
+                .unwrap(),
+            json!({
+                "ids": "996,777",
+                "itunes_ids": "333",
+                "show_latest_episodes": 1
+            })
+        );
+    }
+
+    #[test]
+    fn submit_podcast() {
+        assert!(matches!(
+            SubmitPodcastParams::new("feeds.example.com/rss").build(),
+            Err(Error::ValidationError(_))
+        ));
+        assert_eq!(
+            SubmitPodcastParams::new("https://feeds.example.com/rss").build().unwrap(),
+            json!({ "rss": "https://feeds.example.com/rss" })
+        );
+    }
+}
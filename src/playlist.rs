@@ -0,0 +1,190 @@
+//! Exporting fetched playlists to M3U8 and XSPF files, for media players.
+//!
+//! ```no_run
+//! # tokio_test::block_on(async {
+//! use podcast_api::playlist;
+//!
+//! let client = podcast_api::Client::new(Some("YOUR-API-KEY"));
+//! let fetched = client
+//!     .fetch_playlist_by_id("m1pe7z60bsw", &serde_json::json!({}))
+//!     .await
+//!     .unwrap()
+//!     .playlist()
+//!     .await
+//!     .unwrap();
+//! let tracks = client.playlist_tracks(&fetched, 4).await.unwrap();
+//! std::fs::write("playlist.m3u8", playlist::m3u8(&fetched.name, &tracks)).unwrap();
+//! std::fs::write("playlist.xspf", playlist::xspf(&fetched.name, &tracks)).unwrap();
+//! # });
+//! ```
+use super::{model, Client, Result};
+use quick_xml::escape::escape;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Audio of a playlist item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Track {
+    /// Episode or custom audio title.
+    pub title: String,
+    /// Title of the podcast of the episode.
+    pub podcast: Option<String>,
+    /// Audio url.
+    pub audio: String,
+    /// Audio length, in seconds.
+    pub audio_length_sec: u64,
+    /// Image url.
+    pub image: Option<String>,
+}
+
+impl From<&model::Episode> for Track {
+    fn from(episode: &model::Episode) -> Track {
+        Track {
+            title: episode.title.clone(),
+            podcast: episode.podcast.as_ref().map(|podcast| podcast.title.clone()),
+            audio: episode.audio.clone(),
+            audio_length_sec: episode.audio_length_sec,
+            image: Some(episode.image.clone()).filter(|image| !image.is_empty()),
+        }
+    }
+}
+
+impl From<&model::CustomAudio> for Track {
+    fn from(audio: &model::CustomAudio) -> Track {
+        Track {
+            title: audio.title.clone(),
+            podcast: None,
+            audio: audio.audio.clone(),
+            audio_length_sec: audio.audio_length_sec,
+            image: Some(audio.image.clone()).filter(|image| !image.is_empty()),
+        }
+    }
+}
+
+/// Extended M3U playlist named `name`, with the title and duration of each of `tracks`.
+pub fn m3u8(name: &str, tracks: &[Track]) -> String {
+    let mut m3u8 = format!("#EXTM3U\n#PLAYLIST:{}\n", line(name));
+    for track in tracks {
+        let title = match &track.podcast {
+            Some(podcast) => format!("{} - {}", line(podcast), line(&track.title)),
+            None => line(&track.title),
+        };
+        writeln!(
+            m3u8,
+            "#EXTINF:{},{}\n{}",
+            track.audio_length_sec,
+            title,
+            line(&track.audio)
+        )
+        .unwrap();
+    }
+    m3u8
+}
+
+/// XSPF playlist named `name`, with the title, podcast, image and duration of each of `tracks`.
+pub fn xspf(name: &str, tracks: &[Track]) -> String {
+    let mut xspf = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\">\n  <title>{}</title>\n  <trackList>\n",
+        escape(name.trim())
+    );
+    for track in tracks {
+        xspf.push_str("    <track>\n");
+        writeln!(xspf, "      <location>{}</location>", escape(track.audio.as_str())).unwrap();
+        writeln!(xspf, "      <title>{}</title>", escape(track.title.trim())).unwrap();
+        if let Some(podcast) = &track.podcast {
+            writeln!(xspf, "      <creator>{}</creator>", escape(podcast.trim())).unwrap();
+        }
+        if let Some(image) = &track.image {
+            writeln!(xspf, "      <image>{}</image>", escape(image.as_str())).unwrap();
+        }
+        writeln!(xspf, "      <duration>{}</duration>", track.audio_length_sec * 1000).unwrap();
+        xspf.push_str("    </track>\n");
+    }
+    xspf.push_str("  </trackList>\n</playlist>\n");
+    xspf
+}
+
+impl Client {
+    /// Tracks of the items of `playlist`, in their order, fetching the latest episode of podcast items with up
+    /// to `concurrency` calls at once.
+    ///
+    /// Deleted items, and podcasts whose latest episode is not found, are left out.
+    pub async fn playlist_tracks(&self, playlist: &model::Playlist, concurrency: usize) -> Result<Vec<Track>> {
+        let latest: Vec<&str> = playlist
+            .items
+            .iter()
+            .filter_map(|item| match &item.data {
+                model::PlaylistItemData::Podcast(podcast) => Some(podcast.latest_episode_id.as_str()),
+                _ => None,
+            })
+            .collect();
+        let episodes: HashMap<String, model::Episode> = self
+            .fetch_episodes_by_ids(&latest, concurrency)
+            .await?
+            .found
+            .into_iter()
+            .map(|episode| (episode.id.clone(), episode))
+            .collect();
+
+        Ok(playlist
+            .items
+            .iter()
+            .filter_map(|item| match &item.data {
+                model::PlaylistItemData::Episode(episode) => Some(Track::from(episode.as_ref())),
+                model::PlaylistItemData::CustomAudio(audio) => Some(Track::from(audio)),
+                model::PlaylistItemData::Podcast(podcast) => episodes.get(&podcast.latest_episode_id).map(Track::from),
+                model::PlaylistItemData::Deleted(_) => None,
+            })
+            .collect())
+    }
+}
+
+/// `text` on a single line.
+fn line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracks() -> Vec<Track> {
+        vec![
+            Track {
+                title: "864: Part 2\nof My Conversation".to_owned(),
+                podcast: Some("Star Wars 7x7".to_owned()),
+                audio: "https://www.listennotes.com/e/p/4e7c59e1/".to_owned(),
+                audio_length_sec: 2447,
+                image: Some("https://example.com/a.jpg?w=1&h=1".to_owned()),
+            },
+            Track {
+                title: "Intro <draft>".to_owned(),
+                podcast: None,
+                audio: "https://example.com/intro.m4a".to_owned(),
+                audio_length_sec: 61,
+                image: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn extended_m3u() {
+        assert_eq!(
+            m3u8("Road trip", &tracks()),
+            "#EXTM3U\n#PLAYLIST:Road trip\n\
+             #EXTINF:2447,Star Wars 7x7 - 864: Part 2 of My Conversation\nhttps://www.listennotes.com/e/p/4e7c59e1/\n\
+             #EXTINF:61,Intro <draft>\nhttps://example.com/intro.m4a\n"
+        );
+    }
+
+    #[test]
+    fn xspf_tracks() {
+        let xspf = xspf("Road & trip", &tracks());
+        assert!(xspf.contains("<title>Road &amp; trip</title>"));
+        assert!(xspf.contains("<location>https://www.listennotes.com/e/p/4e7c59e1/</location>"));
+        assert!(xspf.contains("<creator>Star Wars 7x7</creator>"));
+        assert!(xspf.contains("<image>https://example.com/a.jpg?w=1&amp;h=1</image>"));
+        assert!(xspf.contains("<duration>2447000</duration>"));
+        assert!(xspf.contains("<title>Intro &lt;draft&gt;</title>\n      <duration>61000</duration>"));
+    }
+}
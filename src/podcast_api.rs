@@ -0,0 +1,237 @@
+use super::{Client, Response, Result};
+use serde_json::Value;
+use std::future::Future;
+
+/// Endpoints of the Listen API, implemented by [`Client`] and, for tests, by [`FakeClient`](super::FakeClient).
+///
+/// Application code written against this trait can be unit tested without network access:
+/// ```
+/// use podcast_api::{FakeClient, PodcastApi, Result};
+/// use serde_json::json;
+///
+/// async fn podcast_title(api: &impl PodcastApi, id: &str) -> Result<String> {
+///     let podcast = api.fetch_podcast_by_id(id, &json!({})).await?.json().await?;
+///     Ok(podcast["title"].as_str().unwrap_or_default().to_owned())
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let fake = FakeClient::new();
+/// fake.respond("podcasts/*", json!({ "title": "Star Wars 7x7" }));
+/// assert_eq!(podcast_title(&fake, "4d3fe717742d4963a85562e9f84d8c79").await.unwrap(), "Star Wars 7x7");
+/// # });
+/// ```
+pub trait PodcastApi {
+    /// Calls [`GET /search`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-search) with supplied parameters.
+    fn search(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /search_episode_titles`](https://www.listennotes.com/api/docs/#get-api-v2-search_episode_titles) with supplied parameters.
+    fn search_episode_titles(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /typeahead`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-typeahead) with supplied parameters.
+    fn typeahead(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /spellcheck`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-spellcheck) with supplied parameters.
+    fn spellcheck(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /related_searches`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-related_searches) with supplied parameters.
+    fn fetch_related_searches(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /trending_searches`](https://www.listennotes.com/api/docs/#get-api-v2-trending_searches) with supplied parameters.
+    fn fetch_trending_searches(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /best_podcasts`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-best_podcasts) with supplied parameters.
+    fn fetch_best_podcasts(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /podcasts/{id}`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-podcasts-id) with supplied parameters.
+    fn fetch_podcast_by_id(&self, id: &str, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`POST /podcasts`](https://www.listennotes.com/podcast-api/docs/#post-api-v2-podcasts) with supplied parameters.
+    fn batch_fetch_podcasts(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /episodes/{id}`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-episodes-id) with supplied parameters.
+    fn fetch_episode_by_id(&self, id: &str, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`POST /episodes`](https://www.listennotes.com/podcast-api/docs/#post-api-v2-episodes) with supplied parameters.
+    fn batch_fetch_episodes(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /curated_podcasts/{id}`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-curated_podcasts-id) with supplied parameters.
+    fn fetch_curated_podcasts_list_by_id(
+        &self,
+        id: &str,
+        parameters: &Value,
+    ) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /curated_podcasts`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-curated_podcasts) with supplied parameters.
+    fn fetch_curated_podcasts_lists(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /genres`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-genres) with supplied parameters.
+    fn fetch_podcast_genres(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /regions`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-regions) with supplied parameters.
+    fn fetch_podcast_regions(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /languages`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-languages) with supplied parameters.
+    fn fetch_podcast_languages(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /just_listen`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-just_listen) with supplied parameters.
+    fn just_listen(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /podcasts/{id}/recommendations`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-podcasts-id-recommendations) with supplied parameters.
+    fn fetch_recommendations_for_podcast(
+        &self,
+        id: &str,
+        parameters: &Value,
+    ) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /episodes/{id}/recommendations`](https://www.listennotes.com/api/docs/#get-api-v2-episodes-id-recommendations) with supplied parameters.
+    fn fetch_recommendations_for_episode(
+        &self,
+        id: &str,
+        parameters: &Value,
+    ) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /playlists/{id}`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-playlists-id) with supplied parameters.
+    fn fetch_playlist_by_id(&self, id: &str, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /playlists`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-playlists) with supplied parameters.
+    fn fetch_my_playlists(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`POST /podcasts/submit`](https://www.listennotes.com/podcast-api/docs/#post-api-v2-podcasts-submit) with supplied parameters.
+    fn submit_podcast(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`DELETE /podcasts/{id}`](https://www.listennotes.com/podcast-api/docs/#delete-api-v2-podcasts-id) with supplied parameters.
+    fn delete_podcast(&self, id: &str, parameters: &Value) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /podcasts/{id}/audience`](https://www.listennotes.com/podcast-api/docs/#get-api-v2-podcasts-id-audience) with supplied parameters.
+    fn fetch_audience_for_podcast(&self, id: &str, parameters: &Value)
+        -> impl Future<Output = Result<Response>> + Send;
+
+    /// Calls [`GET /podcasts/domains/{domain_name}`](https://www.listennotes.com/api/docs/#get-api-v2-podcasts-domains-domain_name) with supplied parameters.
+    fn fetch_podcasts_by_domain(
+        &self,
+        domain_name: &str,
+        parameters: &Value,
+    ) -> impl Future<Output = Result<Response>> + Send;
+}
+
+impl PodcastApi for Client {
+    fn search(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::search(self, parameters)
+    }
+
+    fn search_episode_titles(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::search_episode_titles(self, parameters)
+    }
+
+    fn typeahead(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::typeahead(self, parameters)
+    }
+
+    fn spellcheck(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::spellcheck(self, parameters)
+    }
+
+    fn fetch_related_searches(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::fetch_related_searches(self, parameters)
+    }
+
+    fn fetch_trending_searches(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::fetch_trending_searches(self, parameters)
+    }
+
+    fn fetch_best_podcasts(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::fetch_best_podcasts(self, parameters)
+    }
+
+    fn fetch_podcast_by_id(&self, id: &str, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::fetch_podcast_by_id(self, id, parameters)
+    }
+
+    fn batch_fetch_podcasts(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::batch_fetch_podcasts(self, parameters)
+    }
+
+    fn fetch_episode_by_id(&self, id: &str, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::fetch_episode_by_id(self, id, parameters)
+    }
+
+    fn batch_fetch_episodes(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::batch_fetch_episodes(self, parameters)
+    }
+
+    fn fetch_curated_podcasts_list_by_id(
+        &self,
+        id: &str,
+        parameters: &Value,
+    ) -> impl Future<Output = Result<Response>> + Send {
+        Client::fetch_curated_podcasts_list_by_id(self, id, parameters)
+    }
+
+    fn fetch_curated_podcasts_lists(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::fetch_curated_podcasts_lists(self, parameters)
+    }
+
+    fn fetch_podcast_genres(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::fetch_podcast_genres(self, parameters)
+    }
+
+    fn fetch_podcast_regions(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::fetch_podcast_regions(self, parameters)
+    }
+
+    fn fetch_podcast_languages(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::fetch_podcast_languages(self, parameters)
+    }
+
+    fn just_listen(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::just_listen(self, parameters)
+    }
+
+    fn fetch_recommendations_for_podcast(
+        &self,
+        id: &str,
+        parameters: &Value,
+    ) -> impl Future<Output = Result<Response>> + Send {
+        Client::fetch_recommendations_for_podcast(self, id, parameters)
+    }
+
+    fn fetch_recommendations_for_episode(
+        &self,
+        id: &str,
+        parameters: &Value,
+    ) -> impl Future<Output = Result<Response>> + Send {
+        Client::fetch_recommendations_for_episode(self, id, parameters)
+    }
+
+    fn fetch_playlist_by_id(&self, id: &str, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::fetch_playlist_by_id(self, id, parameters)
+    }
+
+    fn fetch_my_playlists(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::fetch_my_playlists(self, parameters)
+    }
+
+    fn submit_podcast(&self, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::submit_podcast(self, parameters)
+    }
+
+    fn delete_podcast(&self, id: &str, parameters: &Value) -> impl Future<Output = Result<Response>> + Send {
+        Client::delete_podcast(self, id, parameters)
+    }
+
+    fn fetch_audience_for_podcast(
+        &self,
+        id: &str,
+        parameters: &Value,
+    ) -> impl Future<Output = Result<Response>> + Send {
+        Client::fetch_audience_for_podcast(self, id, parameters)
+    }
+
+    fn fetch_podcasts_by_domain(
+        &self,
+        domain_name: &str,
+        parameters: &Value,
+    ) -> impl Future<Output = Result<Response>> + Send {
+        Client::fetch_podcasts_by_domain(self, domain_name, parameters)
+    }
+}
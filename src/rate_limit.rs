@@ -0,0 +1,115 @@
+use super::client::now;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// Token bucket throttling API calls from a [`Client`](super::Client) to stay under the rate of a plan.
+///
+/// Allows bursts of up to [`burst`](RateLimiter::new) calls, refilled at `requests_per_second`. Calls
+/// beyond that wait until their turn instead of failing with [`Error::RateLimitError`](super::Error::RateLimitError).
+/// Clones share their tokens, so one limiter can throttle several clients and concurrent tasks.
+/// ```
+/// let client = podcast_api::Client::new(None).with_rate_limiter(podcast_api::RateLimiter::new(5.0, 10));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    requests_per_second: f64,
+    burst: f64,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Available tokens, negative when calls are waiting for tokens.
+    tokens: f64,
+    updated: SystemTime,
+}
+
+impl RateLimiter {
+    /// Creates limiter for `requests_per_second` on average, with bursts of up to `burst` calls.
+    ///
+    /// A `requests_per_second` that isn't positive, e.g. 0 or NaN, never refills the bucket, so calls
+    /// beyond the first `burst` wait forever.
+    pub fn new(requests_per_second: f64, burst: u32) -> RateLimiter {
+        let burst = f64::from(burst.max(1));
+        RateLimiter {
+            requests_per_second: requests_per_second.max(f64::MIN_POSITIVE),
+            burst,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: burst,
+                updated: now(),
+            })),
+        }
+    }
+
+    /// Takes a token, waiting until it is available.
+    pub(crate) async fn acquire(&self) {
+        let wait = self.reserve(now());
+        if wait > Duration::ZERO {
+            futures_timer::Delay::new(wait).await;
+        }
+    }
+
+    /// Takes a token, blocking the current thread until it is available.
+    #[cfg(feature = "blocking")]
+    pub(crate) fn acquire_blocking(&self) {
+        std::thread::sleep(self.reserve(now()));
+    }
+
+    /// Takes a token at `now`, and returns how long to wait before it may be used.
+    fn reserve(&self, now: SystemTime) -> Duration {
+        let mut bucket = self.bucket.lock().expect("rate limiter lock poisoned");
+        if let Ok(elapsed) = now.duration_since(bucket.updated) {
+            bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * self.requests_per_second).min(self.burst);
+            bucket.updated = now;
+        }
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::try_from_secs_f64(-bucket.tokens / self.requests_per_second).unwrap_or(Duration::MAX)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burst_then_rate() {
+        let limiter = RateLimiter::new(2.0, 3);
+        let start = limiter.bucket.lock().unwrap().updated;
+        assert_eq!(limiter.reserve(start), Duration::ZERO);
+        assert_eq!(limiter.reserve(start), Duration::ZERO);
+        assert_eq!(limiter.reserve(start), Duration::ZERO);
+        assert_eq!(limiter.reserve(start), Duration::from_millis(500));
+        assert_eq!(limiter.reserve(start), Duration::from_secs(1));
+
+        // Two tokens were refilled, both already reserved by waiting calls.
+        assert_eq!(
+            limiter.reserve(start + Duration::from_secs(1)),
+            Duration::from_millis(500)
+        );
+        // Refills stop at the burst size.
+        assert_eq!(limiter.reserve(start + Duration::from_secs(60)), Duration::ZERO);
+        assert_eq!(limiter.bucket.lock().unwrap().tokens, 2.0);
+    }
+
+    #[test]
+    fn without_rate() {
+        for requests_per_second in [0.0, -1.0, f64::NAN] {
+            let limiter = RateLimiter::new(requests_per_second, 1);
+            let start = limiter.bucket.lock().unwrap().updated;
+            assert_eq!(limiter.reserve(start), Duration::ZERO);
+            assert_eq!(limiter.reserve(start + Duration::from_secs(60)), Duration::MAX);
+        }
+    }
+
+    #[test]
+    fn shared_by_clones() {
+        let limiter = RateLimiter::new(1.0, 1);
+        let start = limiter.bucket.lock().unwrap().updated;
+        assert_eq!(limiter.clone().reserve(start), Duration::ZERO);
+        assert_eq!(limiter.reserve(start), Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,156 @@
+use super::Error;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Policy for retrying failed API calls from [`Client`](super::Client).
+///
+/// Delays grow exponentially from [`base_delay`](RetryPolicy::base_delay) up to
+/// [`max_delay`](RetryPolicy::max_delay), unless the API sends a `Retry-After` header.
+/// ```
+/// use std::time::Duration;
+///
+/// let client = podcast_api::Client::new(None).with_retry_policy(
+///     podcast_api::RetryPolicy::new()
+///         .max_attempts(5)
+///         .base_delay(Duration::from_millis(200))
+///         .retry_if(|err| matches!(err, podcast_api::Error::RateLimitError(_))),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    retryable: fn(&Error) -> bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy::new()
+    }
+}
+
+impl RetryPolicy {
+    /// Creates policy for up to 3 attempts, with delays from 500ms to 30s and jitter.
+    ///
+    /// Retries errors for which [`Error::is_retryable`] holds.
+    pub fn new() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            retryable: Error::is_retryable,
+        }
+    }
+
+    /// Creates policy that never retries.
+    pub fn none() -> RetryPolicy {
+        RetryPolicy::new().max_attempts(1)
+    }
+
+    /// Maximum number of attempts, including the first one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Delay before the first retry, doubled for each further retry.
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Upper bound of any delay, including ones requested by `Retry-After`.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Randomize exponential delays between zero and their full length.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Errors for which the call is retried.
+    pub fn retry_if(mut self, retryable: fn(&Error) -> bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    pub(crate) fn should_retry(&self, attempts: u32, err: &Error) -> bool {
+        attempts < self.max_attempts && (self.retryable)(err)
+    }
+
+    /// Delay after the failed attempt number `attempts`.
+    pub(crate) fn delay(&self, attempts: u32, retry_after: Option<Duration>) -> Duration {
+        let delay = match retry_after {
+            Some(retry_after) => retry_after,
+            None => {
+                let delay = self
+                    .base_delay
+                    .checked_mul(2u32.saturating_pow(attempts.saturating_sub(1)))
+                    .unwrap_or(self.max_delay)
+                    .min(self.max_delay);
+                if self.jitter {
+                    delay.mul_f64(random_fraction())
+                } else {
+                    delay
+                }
+            }
+        };
+        delay.min(self.max_delay)
+    }
+}
+
+/// Random number in `[0, 1)`, good enough for jitter without pulling in a random number generator.
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exponential_delay() {
+        let policy = RetryPolicy::new()
+            .base_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(5))
+            .jitter(false);
+        assert_eq!(policy.delay(1, None), Duration::from_secs(1));
+        assert_eq!(policy.delay(2, None), Duration::from_secs(2));
+        assert_eq!(policy.delay(3, None), Duration::from_secs(4));
+        assert_eq!(policy.delay(4, None), Duration::from_secs(5));
+        assert_eq!(policy.delay(100, None), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn retry_after_delay() {
+        let policy = RetryPolicy::new().max_delay(Duration::from_secs(10));
+        assert_eq!(policy.delay(1, Some(Duration::from_secs(7))), Duration::from_secs(7));
+        assert_eq!(policy.delay(1, Some(Duration::from_secs(60))), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn jitter_delay() {
+        let policy = RetryPolicy::new().base_delay(Duration::from_secs(1));
+        assert!(policy.delay(1, None) <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn should_retry() {
+        let policy = RetryPolicy::new().max_attempts(2);
+        let url = reqwest::Url::parse("https://listen-api.listennotes.com/api/v2/search").unwrap();
+        let connection = Error::connection(&http::Method::GET, &url, None);
+        assert!(policy.should_retry(1, &connection));
+        assert!(!policy.should_retry(2, &connection));
+        assert!(!policy.should_retry(1, &Error::ValidationError("q is required".to_owned())));
+        assert!(!RetryPolicy::none().should_retry(1, &connection));
+    }
+}
@@ -0,0 +1,181 @@
+//! RSS 2.0 feeds with the iTunes namespace, rendered from fetched podcasts.
+//!
+//! ```no_run
+//! # tokio_test::block_on(async {
+//! let client = podcast_api::Client::new(Some("YOUR-API-KEY"));
+//! let podcast = client
+//!     .fetch_podcast_by_id("4d3fe717742d4963a85562e9f84d8c79", &serde_json::json!({}))
+//!     .await
+//!     .unwrap()
+//!     .podcast()
+//!     .await
+//!     .unwrap();
+//! std::fs::write("feed.xml", podcast_api::rss::write(&podcast)).unwrap();
+//! # });
+//! ```
+use super::model;
+use quick_xml::escape::escape;
+use std::fmt::Write;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Feed of `podcast` with an item for each of its `episodes`, in their order.
+///
+/// Enclosures have a length of 0, since the API only returns the duration of episodes.
+pub fn write(podcast: &model::Podcast) -> String {
+    let link = podcast
+        .website
+        .as_deref()
+        .filter(|website| !website.is_empty())
+        .unwrap_or(&podcast.listennotes_url);
+
+    let mut rss = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\" xmlns:itunes=\"http://www.itunes.com/dtds/podcast-1.0.dtd\">\n  <channel>\n",
+    );
+    element(&mut rss, 4, "title", podcast.title.trim());
+    element(&mut rss, 4, "link", link);
+    element(&mut rss, 4, "description", &podcast.description);
+    if podcast.latest_pub_date_ms > 0 {
+        element(&mut rss, 4, "pubDate", &date(podcast.latest_pub_date_ms));
+    }
+    rss.push_str("    <image>\n");
+    element(&mut rss, 6, "url", &podcast.image);
+    element(&mut rss, 6, "title", podcast.title.trim());
+    element(&mut rss, 6, "link", link);
+    rss.push_str("    </image>\n");
+    writeln!(rss, "    <itunes:image href=\"{}\"/>", escape(podcast.image.as_str())).unwrap();
+    if let Some(publisher) = podcast.publisher.as_deref() {
+        element(&mut rss, 4, "itunes:author", publisher);
+    }
+    if let Some(email) = podcast.email.as_deref().filter(|email| !email.is_empty()) {
+        rss.push_str("    <itunes:owner>\n");
+        if let Some(publisher) = podcast.publisher.as_deref() {
+            element(&mut rss, 6, "itunes:name", publisher);
+        }
+        element(&mut rss, 6, "itunes:email", email);
+        rss.push_str("    </itunes:owner>\n");
+    }
+    element(&mut rss, 4, "itunes:explicit", explicit(podcast.explicit_content));
+    if !podcast.kind.is_empty() {
+        element(&mut rss, 4, "itunes:type", &podcast.kind);
+    }
+
+    for episode in &podcast.episodes {
+        rss.push_str("    <item>\n");
+        element(&mut rss, 6, "title", episode.title.trim());
+        element(
+            &mut rss,
+            6,
+            "link",
+            episode.link.as_deref().unwrap_or(&episode.listennotes_url),
+        );
+        element(&mut rss, 6, "description", &episode.description);
+        writeln!(
+            rss,
+            "      <guid isPermaLink=\"false\">{}</guid>",
+            escape(episode.guid_from_rss.as_deref().unwrap_or(&episode.id))
+        )
+        .unwrap();
+        element(&mut rss, 6, "pubDate", &date(episode.pub_date_ms));
+        writeln!(
+            rss,
+            "      <enclosure url=\"{}\" length=\"0\" type=\"{}\"/>",
+            escape(episode.audio.as_str()),
+            media_type(&episode.audio)
+        )
+        .unwrap();
+        element(&mut rss, 6, "itunes:duration", &duration(episode.audio_length_sec));
+        element(&mut rss, 6, "itunes:explicit", explicit(episode.explicit_content));
+        if !episode.image.is_empty() {
+            writeln!(rss, "      <itunes:image href=\"{}\"/>", escape(episode.image.as_str())).unwrap();
+        }
+        rss.push_str("    </item>\n");
+    }
+    rss.push_str("  </channel>\n</rss>\n");
+    rss
+}
+
+fn element(rss: &mut String, indent: usize, name: &str, text: &str) {
+    writeln!(
+        rss,
+        "{:indent$}<{name}>{}</{name}>",
+        "",
+        escape(text),
+        indent = indent,
+        name = name
+    )
+    .unwrap();
+}
+
+/// RFC 822 date of `ms` since the Unix epoch.
+fn date(ms: u64) -> String {
+    httpdate::fmt_http_date(UNIX_EPOCH + Duration::from_millis(ms))
+}
+
+/// `HH:MM:SS` of `seconds`.
+fn duration(seconds: u64) -> String {
+    format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+fn explicit(explicit: bool) -> &'static str {
+    if explicit {
+        "true"
+    } else {
+        "false"
+    }
+}
+
+/// Media type of an audio url by its extension, MP3 if unknown, e.g. for Listen Notes audio urls.
+fn media_type(url: &str) -> &'static str {
+    let path = url.split(['?', '#']).next().unwrap_or_default().to_ascii_lowercase();
+    match path.rsplit_once('.').map(|(_, extension)| extension) {
+        Some("m4a") => "audio/x-m4a",
+        Some("aac") => "audio/aac",
+        Some("ogg") | Some("oga") => "audio/ogg",
+        Some("opus") => "audio/opus",
+        Some("wav") => "audio/wav",
+        Some("mp4") => "video/mp4",
+        _ => "audio/mpeg",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    #[test]
+    fn podcast_feed() {
+        let mut podcast: model::Podcast = serde_json::from_str(include_str!("../fixtures/podcast.json")).unwrap();
+        podcast.title = "Star Wars <7x7> & more".to_owned();
+        podcast.episodes[0].explicit_content = true;
+        let rss = write(&podcast);
+
+        let mut reader = Reader::from_str(&rss);
+        let mut items = 0;
+        loop {
+            match reader.read_event().unwrap() {
+                Event::Start(element) if element.name().as_ref() == b"item" => items += 1,
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        assert_eq!(items, podcast.episodes.len());
+        assert!(rss.contains("<title>Star Wars &lt;7x7&gt; &amp; more</title>"));
+        assert!(rss.contains("<itunes:type>episodic</itunes:type>"));
+        assert!(rss.contains(
+            "<enclosure url=\"https://www.listennotes.com/e/p/4e7c59e10e4640b98f2f3cb1777dbb43/\" length=\"0\" type=\"audio/mpeg\"/>"
+        ));
+        assert!(rss.contains("<guid isPermaLink=\"false\">bbada2b3a99054ce93b0eb95dd762b4d</guid>"));
+        assert!(rss.contains("<pubDate>Mon, 14 Nov 2016 08:00:02 GMT</pubDate>"));
+        assert!(
+            rss.contains("<itunes:duration>00:40:47</itunes:duration>\n      <itunes:explicit>true</itunes:explicit>")
+        );
+    }
+
+    #[test]
+    fn media_types() {
+        assert_eq!(media_type("https://example.com/a.M4A?token=1"), "audio/x-m4a");
+        assert_eq!(media_type("https://www.listennotes.com/e/p/4e7c59e1/"), "audio/mpeg");
+    }
+}
@@ -0,0 +1,292 @@
+//! Streams that transparently paginate through [Listen API](https://www.listennotes.com/api/docs/) results.
+use super::params::EpisodeSort;
+use super::{model, Client, Result};
+use futures::Stream;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+#[cfg(not(target_arch = "wasm32"))]
+type Page<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+// Requests of the wasm HTTP client are JavaScript promises, which cannot be sent between threads.
+#[cfg(target_arch = "wasm32")]
+type Page<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + 'a>>;
+
+/// Stream of individual results of [`Client::search`], created by [`Client::search_stream`].
+///
+/// Follows `next_offset` until `total` results were fetched, the API returns an empty page or
+/// the [`limit`](SearchStream::limit) is reached. Pages are only fetched when the stream is polled.
+pub struct SearchStream<'a, T> {
+    client: &'a Client,
+    parameters: Map<String, Value>,
+    offset: u64,
+    limit: Option<usize>,
+    prefetch: bool,
+    yielded: usize,
+    buffer: VecDeque<T>,
+    page: Option<Page<'a, model::SearchResults<T>>>,
+    done: bool,
+}
+
+// Results are only ever moved out of the buffer, never pinned.
+impl<T> Unpin for SearchStream<'_, T> {}
+
+impl<'a, T> SearchStream<'a, T>
+where
+    T: DeserializeOwned + Send + 'a,
+{
+    pub(crate) fn new(client: &'a Client, parameters: &Value) -> SearchStream<'a, T> {
+        let parameters = parameters.as_object().cloned().unwrap_or_default();
+        let offset = parameters.get("offset").and_then(Value::as_u64).unwrap_or(0);
+        SearchStream {
+            client,
+            parameters,
+            offset,
+            limit: None,
+            prefetch: false,
+            yielded: 0,
+            buffer: VecDeque::new(),
+            page: None,
+            done: false,
+        }
+    }
+
+    /// Stops the stream after `limit` results.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Requests the next page as soon as the current one starts being consumed, instead of once it's exhausted.
+    pub fn prefetch(mut self, prefetch: bool) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
+    fn wants_page(&self) -> bool {
+        if self.done || self.page.is_some() {
+            return false;
+        }
+        if let Some(limit) = self.limit {
+            if self.yielded + self.buffer.len() >= limit {
+                return false;
+            }
+        }
+        self.buffer.is_empty() || self.prefetch
+    }
+
+    fn fetch_page(&mut self) {
+        let client = self.client;
+        let mut parameters = self.parameters.clone();
+        parameters.insert("offset".to_owned(), Value::from(self.offset));
+        self.page = Some(Box::pin(async move {
+            client.search(&Value::Object(parameters)).await?.parse().await
+        }));
+    }
+
+    fn add_page(&mut self, page: model::SearchResults<T>) {
+        if page.results.is_empty() || page.next_offset >= page.total || page.next_offset <= self.offset {
+            self.done = true;
+        }
+        self.offset = page.next_offset;
+        self.buffer.extend(page.results);
+    }
+}
+
+impl<'a, T> Stream for SearchStream<'a, T>
+where
+    T: DeserializeOwned + Send + 'a,
+{
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if matches!(this.limit, Some(limit) if this.yielded >= limit) {
+                return Poll::Ready(None);
+            }
+            if this.wants_page() {
+                this.fetch_page();
+            }
+            if let Some(page) = this.page.as_mut() {
+                if let Poll::Ready(page) = page.as_mut().poll(cx) {
+                    this.page = None;
+                    match page {
+                        Ok(page) => this.add_page(page),
+                        Err(err) => {
+                            this.done = true;
+                            return Poll::Ready(Some(Err(err)));
+                        }
+                    }
+                    // Start the following page right away if prefetching.
+                    continue;
+                }
+            }
+            return match this.buffer.pop_front() {
+                Some(result) => {
+                    this.yielded += 1;
+                    Poll::Ready(Some(Ok(result)))
+                }
+                None if this.page.is_some() => Poll::Pending,
+                None => Poll::Ready(None),
+            };
+        }
+    }
+}
+
+/// Position of an [`EpisodeStream`] after its last yielded episode, to resume from.
+///
+/// It can be serialized, e.g. to resume in a later run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpisodeCursor {
+    /// Published date of the last yielded episode, in milliseconds.
+    pub pub_date_ms: u64,
+    /// Ids of the yielded episodes published at `pub_date_ms`, so they are not yielded again
+    /// when the next page includes that date.
+    pub ids: Vec<String>,
+}
+
+/// Stream of every episode of a podcast, created by [`Client::podcast_episodes_stream`].
+///
+/// Follows `next_episode_pub_date` of [`Client::fetch_podcast_by_id`] until the podcast's earliest
+/// (or, for [`EpisodeSort::OldestFirst`], latest) episode was yielded, or the API stops moving it forward.
+pub struct EpisodeStream<'a> {
+    client: &'a Client,
+    id: String,
+    sort: EpisodeSort,
+    cursor: Option<EpisodeCursor>,
+    buffer: VecDeque<model::Episode>,
+    page: Option<Page<'a, model::Podcast>>,
+    done: bool,
+}
+
+impl<'a> EpisodeStream<'a> {
+    pub(crate) fn new(client: &'a Client, id: &str, sort: EpisodeSort) -> EpisodeStream<'a> {
+        EpisodeStream {
+            client,
+            id: id.to_owned(),
+            sort,
+            cursor: None,
+            buffer: VecDeque::new(),
+            page: None,
+            done: false,
+        }
+    }
+
+    /// Resumes after the episodes a previous stream's [`cursor`](EpisodeStream::cursor) pointed to.
+    pub fn resume_from(mut self, cursor: EpisodeCursor) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
+    /// Cursor to resume from after the last yielded episode, e.g. after a failure, or the
+    /// [`resume_from`](EpisodeStream::resume_from) cursor if nothing was yielded yet.
+    pub fn cursor(&self) -> Option<&EpisodeCursor> {
+        self.cursor.as_ref()
+    }
+
+    fn fetch_page(&mut self) {
+        let client = self.client;
+        let id = self.id.clone();
+        let mut parameters = Map::new();
+        parameters.insert("sort".to_owned(), Value::from(self.sort.as_str()));
+        if let Some(cursor) = &self.cursor {
+            parameters.insert("next_episode_pub_date".to_owned(), Value::from(cursor.pub_date_ms));
+        }
+        self.page = Some(Box::pin(async move {
+            client
+                .fetch_podcast_by_id(&id, &Value::Object(parameters))
+                .await?
+                .parse()
+                .await
+        }));
+    }
+
+    /// Whether `pub_date_ms` comes after the cursor in the sort order.
+    fn is_past_cursor(&self, pub_date_ms: u64) -> bool {
+        match (&self.cursor, self.sort) {
+            (None, _) => true,
+            (Some(cursor), EpisodeSort::RecentFirst) => pub_date_ms < cursor.pub_date_ms,
+            (Some(cursor), EpisodeSort::OldestFirst) => pub_date_ms > cursor.pub_date_ms,
+        }
+    }
+
+    /// Whether `episode` was not yielded yet, by this stream or the one it resumes.
+    fn is_new(&self, episode: &model::Episode) -> bool {
+        match &self.cursor {
+            Some(cursor) if cursor.pub_date_ms == episode.pub_date_ms => !cursor.ids.contains(&episode.id),
+            _ => self.is_past_cursor(episode.pub_date_ms),
+        }
+    }
+
+    fn add_page(&mut self, podcast: model::Podcast) {
+        let last_pub_date_ms = match self.sort {
+            EpisodeSort::RecentFirst => podcast.earliest_pub_date_ms,
+            EpisodeSort::OldestFirst => podcast.latest_pub_date_ms,
+        };
+        if podcast.episodes.is_empty()
+            || !podcast
+                .next_episode_pub_date
+                .is_some_and(|next| self.is_past_cursor(next))
+            || podcast
+                .episodes
+                .iter()
+                .any(|episode| episode.pub_date_ms == last_pub_date_ms)
+        {
+            self.done = true;
+        }
+        let episodes: Vec<_> = podcast
+            .episodes
+            .into_iter()
+            .filter(|episode| self.is_new(episode))
+            .collect();
+        self.buffer.extend(episodes);
+    }
+}
+
+impl Stream for EpisodeStream<'_> {
+    type Item = Result<model::Episode>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(episode) = this.buffer.pop_front() {
+                match &mut this.cursor {
+                    Some(cursor) if cursor.pub_date_ms == episode.pub_date_ms => cursor.ids.push(episode.id.clone()),
+                    cursor => {
+                        *cursor = Some(EpisodeCursor {
+                            pub_date_ms: episode.pub_date_ms,
+                            ids: vec![episode.id.clone()],
+                        })
+                    }
+                }
+                return Poll::Ready(Some(Ok(episode)));
+            }
+            if this.done {
+                return Poll::Ready(None);
+            }
+            if this.page.is_none() {
+                this.fetch_page();
+            }
+            if let Some(page) = this.page.as_mut() {
+                match page.as_mut().poll(cx) {
+                    Poll::Ready(Ok(podcast)) => {
+                        this.page = None;
+                        this.add_page(podcast);
+                    }
+                    Poll::Ready(Err(err)) => {
+                        // Keep the cursor, so the stream can be resumed from where it failed.
+                        this.page = None;
+                        this.done = true;
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use http::HeaderMap;
+use std::time::Duration;
+
+/// Plan usage reported by the Listen API in response headers.
+///
+/// Each value is `None` if the header was missing or malformed, e.g. for the mock API.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Usage {
+    /// Number of free requests per billing cycle, from `X-ListenAPI-FreeQuota`.
+    pub free_quota: Option<u64>,
+    /// Number of requests made in the current billing cycle, from `X-ListenAPI-Usage`.
+    pub usage: Option<u64>,
+    /// Start of the next billing cycle, e.g. `2021-04-20T00:00:00+00:00`, from `X-ListenAPI-NextBillingDate`.
+    pub next_billing_date: Option<String>,
+    /// Server side processing time of the request, from `X-ListenAPI-Latency-Seconds`.
+    pub latency: Option<Duration>,
+    /// Unique id of the request, from `X-Request-ID`, to include when contacting support.
+    pub request_id: Option<String>,
+}
+
+impl Usage {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Usage {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        Usage {
+            free_quota: header("x-listenapi-freequota").and_then(|value| value.parse().ok()),
+            usage: header("x-listenapi-usage").and_then(|value| value.parse().ok()),
+            next_billing_date: header("x-listenapi-nextbillingdate").map(str::to_owned),
+            latency: header("x-listenapi-latency-seconds").and_then(parse_latency),
+            request_id: header("x-request-id").map(str::to_owned),
+        }
+    }
+
+    /// Remaining free requests in the current billing cycle, if both quota and usage are known.
+    pub fn remaining_free_quota(&self) -> Option<u64> {
+        Some(self.free_quota?.saturating_sub(self.usage?))
+    }
+
+    /// Updates values with the ones present in a newer `usage`.
+    pub(crate) fn update(&mut self, usage: Usage) {
+        if usage.free_quota.is_some() {
+            self.free_quota = usage.free_quota;
+        }
+        if usage.usage.is_some() {
+            self.usage = usage.usage;
+        }
+        if usage.next_billing_date.is_some() {
+            self.next_billing_date = usage.next_billing_date;
+        }
+        if usage.latency.is_some() {
+            self.latency = usage.latency;
+        }
+        if usage.request_id.is_some() {
+            self.request_id = usage.request_id;
+        }
+    }
+}
+
+/// Latency is given in seconds, e.g. `0.025`.
+fn parse_latency(value: &str) -> Option<Duration> {
+    let seconds: f64 = value.parse().ok()?;
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    #[test]
+    fn from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-ListenAPI-FreeQuota", HeaderValue::from_static("300"));
+        headers.insert("X-ListenAPI-Usage", HeaderValue::from_static("120"));
+        headers.insert(
+            "X-ListenAPI-NextBillingDate",
+            HeaderValue::from_static("2021-04-20T00:00:00+00:00"),
+        );
+        headers.insert("X-ListenAPI-Latency-Seconds", HeaderValue::from_static("0.025"));
+        headers.insert("X-Request-ID", HeaderValue::from_static("abc123"));
+        let usage = Usage::from_headers(&headers);
+        assert_eq!(usage.free_quota, Some(300));
+        assert_eq!(usage.usage, Some(120));
+        assert_eq!(usage.remaining_free_quota(), Some(180));
+        assert_eq!(usage.next_billing_date.as_deref(), Some("2021-04-20T00:00:00+00:00"));
+        assert_eq!(usage.latency, Some(Duration::from_millis(25)));
+        assert_eq!(usage.request_id.as_deref(), Some("abc123"));
+        assert_eq!(parse_latency("-1"), None);
+        assert_eq!(parse_latency("NaN"), None);
+    }
+
+    #[test]
+    fn update() {
+        let mut usage = Usage {
+            free_quota: Some(300),
+            usage: Some(120),
+            ..Default::default()
+        };
+        usage.update(Usage {
+            usage: Some(121),
+            request_id: Some("abc124".to_owned()),
+            ..Default::default()
+        });
+        assert_eq!(usage.free_quota, Some(300));
+        assert_eq!(usage.usage, Some(121));
+        assert_eq!(usage.request_id.as_deref(), Some("abc124"));
+        assert_eq!(Usage::from_headers(&HeaderMap::new()), Usage::default());
+    }
+}
@@ -1,3 +1,6 @@
+#![cfg(not(target_arch = "wasm32"))]
+#![allow(clippy::len_zero, clippy::match_like_matches_macro)]
+
 macro_rules! b {
     ($e:expr) => {
         tokio_test::block_on($e)
@@ -5,11 +8,25 @@ macro_rules! b {
 }
 
 mod mock {
+    use futures::TryStreamExt;
     use serde_json::json;
     use std::borrow::Cow;
 
-    fn client<'a>() -> podcast_api::Client<'a> {
-        podcast_api::Client::new(None)
+    use podcast_api::MockServer;
+    use std::sync::OnceLock;
+    use std::time::Duration;
+
+    fn client() -> podcast_api::Client {
+        static SERVER: OnceLock<MockServer> = OnceLock::new();
+        SERVER.get_or_init(MockServer::start).client()
+    }
+
+    fn client_with_key(server: &MockServer, api_key: &str) -> podcast_api::Client {
+        podcast_api::Client::builder()
+            .api_key(api_key)
+            .base_url(server.url())
+            .build()
+            .unwrap()
     }
 
     #[test]
@@ -36,6 +53,23 @@ mod mock {
         });
     }
 
+    #[test]
+    fn search_stream() {
+        b!(async {
+            let client = client();
+            let results: Vec<podcast_api::model::SearchEpisode> = client
+                .search_stream(&json!({
+                    "q": "dummy"
+                }))
+                .limit(3)
+                .prefetch(true)
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(results.len(), 3);
+        });
+    }
+
     #[test]
     fn search_episode_titles() {
         b!(async {
@@ -63,19 +97,310 @@ mod mock {
     #[test]
     fn search_with_authentication_error() {
         b!(async {
-            let response = podcast_api::Client::new(Some("wrong_key"))
+            let server = MockServer::start();
+            server.require_api_key("right_key");
+            let response = client_with_key(&server, "wrong_key")
                 .search(&json!({
                     "q": "dummy",
                     "sort_by_date": 1
                 }))
                 .await;
             assert!(match response {
-                Err(podcast_api::Error::AuthenticationError) => true,
+                Err(podcast_api::Error::AuthenticationError(_)) => true,
                 _ => false,
             });
         });
     }
 
+    #[test]
+    fn search_with_builder_headers() {
+        b!(async {
+            let server = MockServer::start();
+            podcast_api::Client::builder()
+                .base_url(server.url())
+                .header(
+                    http::HeaderName::from_static("x-team"),
+                    http::HeaderValue::from_static("podcasts"),
+                )
+                .user_agent("my-app")
+                .build()
+                .unwrap()
+                .search(&json!({
+                    "q": "dummy"
+                }))
+                .await
+                .unwrap();
+            let headers = server.last_request_headers().unwrap();
+            assert_eq!(headers["x-team"], "podcasts");
+            assert_eq!(headers[http::header::USER_AGENT], "my-app");
+        });
+    }
+
+    #[test]
+    fn search_with_connection_error() {
+        b!(async {
+            let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+            let response = podcast_api::Client::builder()
+                .base_url(format!("http://127.0.0.1:{}/api/v2", port))
+                .build()
+                .unwrap()
+                .search(&json!({
+                    "q": "dummy"
+                }))
+                .await;
+            match response {
+                Err(err @ podcast_api::Error::ApiConnectionError { .. }) => {
+                    assert!(err.to_string().contains("GET /api/v2/search"));
+                    assert!(std::error::Error::source(&err).is_some());
+                }
+                _ => panic!("expected ApiConnectionError"),
+            }
+        });
+    }
+
+    #[test]
+    fn search_with_retry_policy() {
+        b!(async {
+            let response = client()
+                .with_retry_policy(podcast_api::RetryPolicy::new().max_attempts(3))
+                .search(&json!({
+                    "q": "dummy"
+                }))
+                .await
+                .unwrap();
+            assert_eq!(response.attempts, 1);
+            let server = MockServer::start();
+            server.require_api_key("right_key");
+            let response = client_with_key(&server, "wrong_key")
+                .with_retry_policy(podcast_api::RetryPolicy::new().max_attempts(3))
+                .search(&json!({
+                    "q": "dummy"
+                }))
+                .await;
+            assert!(matches!(response, Err(podcast_api::Error::AuthenticationError(_))));
+        });
+    }
+
+    #[test]
+    fn search_with_injected_errors() {
+        b!(async {
+            let server = MockServer::start();
+            let client = server.client();
+            let parameters = json!({ "q": "dummy" });
+            server.fail_next(http::StatusCode::NOT_FOUND);
+            server.fail_next(http::StatusCode::TOO_MANY_REQUESTS);
+            server.fail_next(http::StatusCode::INTERNAL_SERVER_ERROR);
+            let response = client.search(&parameters).await;
+            assert!(matches!(response, Err(podcast_api::Error::NotFoundError(_))));
+            let response = client.search(&parameters).await;
+            assert!(matches!(response, Err(podcast_api::Error::RateLimitError(_))));
+            let response = client.search(&parameters).await;
+            assert!(matches!(response, Err(podcast_api::Error::ListenApiError(_))));
+            assert!(client.search(&parameters).await.is_ok());
+
+            server.fail_next(http::StatusCode::SERVICE_UNAVAILABLE);
+            let response = client
+                .clone()
+                .with_retry_policy(podcast_api::RetryPolicy::new().base_delay(std::time::Duration::from_millis(1)))
+                .search(&parameters)
+                .await
+                .unwrap();
+            assert_eq!(response.attempts, 2);
+            assert_eq!(server.requests(), 6);
+
+            server.fail_next(http::StatusCode::SERVICE_UNAVAILABLE);
+            server.fail_next(http::StatusCode::SERVICE_UNAVAILABLE);
+            let response = client
+                .with_retry_policy(
+                    podcast_api::RetryPolicy::new()
+                        .max_attempts(2)
+                        .base_delay(std::time::Duration::from_millis(1)),
+                )
+                .search(&parameters)
+                .await;
+            match response {
+                Err(err @ podcast_api::Error::ServiceUnavailableError(_)) => assert_eq!(err.attempts(), Some(2)),
+                _ => panic!("expected ServiceUnavailableError"),
+            }
+        });
+    }
+
+    #[test]
+    fn search_with_invalid_parameters() {
+        b!(async {
+            let response = client().search(&json!({ "sort_by_date": 1 })).await;
+            match response {
+                Err(podcast_api::Error::InvalidRequestError(context)) => {
+                    assert_eq!(context.endpoint, "/api/v2/search");
+                    assert_eq!(context.message(), Some("Missing required parameter: q"));
+                }
+                _ => panic!("expected InvalidRequestError"),
+            }
+            let response = client().search(&json!({ "q": "dummy", "foo": 1 })).await;
+            assert!(matches!(response, Err(podcast_api::Error::InvalidRequestError(_))));
+        });
+    }
+
+    #[test]
+    fn search_with_cassette() {
+        b!(async {
+            let path = std::env::temp_dir().join(format!("podcast-api-cassette-{}.json", std::process::id()));
+            let parameters = json!({ "q": "dummy" });
+            {
+                let server = MockServer::start();
+                let client = client_with_key(&server, "secret_key").with_cassette(podcast_api::Cassette::record(&path));
+                client.search(&parameters).await.unwrap();
+                client.cassette().unwrap().save().unwrap();
+                assert_eq!(podcast_api::Cassette::replay(&path).unwrap().len(), 1);
+                server.fail_next(http::StatusCode::NOT_FOUND);
+                assert!(client.fetch_podcast_by_id("dummy_id", &json!({})).await.is_err());
+            }
+            assert!(!std::fs::read_to_string(&path).unwrap().contains("secret_key"));
+
+            let cassette = podcast_api::Cassette::replay(&path).unwrap();
+            assert_eq!(cassette.len(), 2);
+            let client = podcast_api::Client::new(None).with_cassette(cassette);
+            let response = client.search(&parameters).await.unwrap();
+            assert!(!response.search_results().await.unwrap().results.is_empty());
+            let response = client.fetch_podcast_by_id("dummy_id", &json!({})).await;
+            match response {
+                Err(podcast_api::Error::NotFoundError(context)) => {
+                    assert_eq!(context.endpoint, "/api/v2/podcasts/dummy_id")
+                }
+                _ => panic!("expected NotFoundError"),
+            }
+            let response = client.search(&parameters).await;
+            assert!(matches!(response, Err(podcast_api::Error::CassetteError(_))));
+            std::fs::remove_file(&path).unwrap();
+        });
+    }
+
+    #[test]
+    fn fetch_podcast_genres_cached() {
+        b!(async {
+            let server = MockServer::start();
+            let client = server.client().with_cache(podcast_api::ResponseCache::new());
+            let response = client
+                .fetch_podcast_genres(&json!({ "top_level_only": 1 }))
+                .await
+                .unwrap();
+            assert!(!response.cache_hit);
+            assert_eq!(response.attempts, 1);
+            assert_eq!(response.usage().usage, Some(1));
+            let response = client
+                .fetch_podcast_genres(&json!({ "top_level_only": 1 }))
+                .await
+                .unwrap();
+            assert!(response.cache_hit);
+            assert_eq!(response.attempts, 0);
+            assert_eq!(response.usage(), podcast_api::Usage::default());
+            assert!(!response.genres().await.unwrap().genres.is_empty());
+            assert_eq!(server.requests(), 1);
+
+            let response = client.fetch_podcast_genres(&json!({})).await.unwrap();
+            assert!(!response.cache_hit);
+            let response = client.search(&json!({ "q": "dummy" })).await.unwrap();
+            assert!(!response.cache_hit);
+            let response = client.search(&json!({ "q": "dummy" })).await.unwrap();
+            assert!(!response.cache_hit);
+            assert_eq!(server.requests(), 4);
+        });
+    }
+
+    #[test]
+    fn fetch_podcast_by_id_cached_on_disk() {
+        b!(async {
+            let dir = std::env::temp_dir().join(format!("podcast-api-disk-cache-{}", std::process::id()));
+            let server = MockServer::start();
+            let cached_client = || {
+                let store = podcast_api::DiskStore::new(&dir).unwrap();
+                server.client().with_cache(
+                    podcast_api::ResponseCache::with_store(store).ttl("podcasts/*", Duration::from_secs(60)),
+                )
+            };
+            let response = cached_client()
+                .fetch_podcast_by_id("dummy_id", &json!({}))
+                .await
+                .unwrap();
+            assert!(!response.cache_hit);
+            // As if the process restarted.
+            let response = cached_client()
+                .fetch_podcast_by_id("dummy_id", &json!({}))
+                .await
+                .unwrap();
+            assert!(response.cache_hit);
+            assert!(!response.podcast().await.unwrap().episodes.is_empty());
+            assert_eq!(server.requests(), 1);
+            std::fs::remove_dir_all(&dir).unwrap();
+        });
+    }
+
+    #[test]
+    fn search_rate_limited() {
+        b!(async {
+            let server = MockServer::start();
+            let client = server
+                .client()
+                .with_rate_limiter(podcast_api::RateLimiter::new(20.0, 2));
+            let start = std::time::Instant::now();
+            for _ in 0..4 {
+                client.clone().search(&json!({ "q": "dummy" })).await.unwrap();
+            }
+            // Two calls of the burst right away, then one every 50ms.
+            assert!(start.elapsed() >= Duration::from_millis(100));
+            assert_eq!(server.requests(), 4);
+        });
+    }
+
+    async fn genre_names(api: &impl podcast_api::PodcastApi) -> Vec<String> {
+        let genres = api
+            .fetch_podcast_genres(&json!({}))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        genres["genres"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|genre| genre["name"].as_str().unwrap().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn podcast_api_trait() {
+        fn assert_send<T: Send>(_: &T) {}
+        b!(async {
+            let fake = podcast_api::FakeClient::new();
+            fake.respond(
+                "genres",
+                json!({ "genres": [{ "id": 1, "name": "Fake", "parent_id": null }] }),
+            );
+            assert_eq!(genre_names(&fake).await, vec!["Fake".to_owned()]);
+            assert!(!genre_names(&client()).await.is_empty());
+
+            let parameters = json!({});
+            assert_send(&podcast_api::PodcastApi::search(&client(), &parameters));
+            assert_send(&podcast_api::PodcastApi::search(&fake, &parameters));
+        });
+    }
+
+    #[test]
+    fn search_usage() {
+        b!(async {
+            let client = client();
+            let response = client
+                .search(&json!({
+                    "q": "dummy"
+                }))
+                .await
+                .unwrap();
+            assert_eq!(client.usage(), response.usage());
+            assert_eq!(response.usage().latency, Some(Duration::from_millis(1)));
+        });
+    }
+
     #[test]
     fn typeahead() {
         b!(async {
@@ -198,6 +523,172 @@ mod mock {
         });
     }
 
+    #[test]
+    fn fetch_podcast_by_id_typed() {
+        b!(async {
+            let response = client().fetch_podcast_by_id("dummy_id", &json!({})).await.unwrap();
+            let podcast = response.podcast().await.unwrap();
+            assert!(!podcast.id.is_empty());
+            assert!(!podcast.episodes.is_empty());
+            assert!(podcast.episodes[0].podcast.is_none());
+        });
+    }
+
+    #[test]
+    fn podcast_episodes_stream() {
+        b!(async {
+            let client = client();
+            let mut episodes =
+                client.podcast_episodes_stream("dummy_id", podcast_api::params::EpisodeSort::RecentFirst);
+            let episode = episodes.try_next().await.unwrap().unwrap();
+            let cursor = podcast_api::EpisodeCursor {
+                pub_date_ms: episode.pub_date_ms,
+                ids: vec![episode.id.clone()],
+            };
+            assert_eq!(episodes.cursor(), Some(&cursor));
+            let resumed = client
+                .podcast_episodes_stream("dummy_id", podcast_api::params::EpisodeSort::RecentFirst)
+                .resume_from(cursor.clone());
+            assert_eq!(resumed.cursor(), Some(&cursor));
+            // The mock server answers every page with the same podcast, whose cursor never moves forward.
+            let rest: Vec<_> = episodes.try_collect().await.unwrap();
+            assert_eq!(rest.len(), 9);
+            assert!(rest.iter().all(|other| other.id != episode.id));
+        });
+    }
+
+    /// Cassette replaying `pages` of podcast `dummy_id`, each answering the request with its cursor.
+    fn episode_pages(name: &str, pages: &[(Option<u64>, u16, serde_json::Value)]) -> podcast_api::Cassette {
+        let path = std::env::temp_dir().join(format!("podcast-api-{}-{}.json", name, std::process::id()));
+        let interactions: Vec<_> = pages
+            .iter()
+            .map(|(cursor, status, body)| {
+                let query = match cursor {
+                    Some(cursor) => format!("next_episode_pub_date={}&sort=recent_first", cursor),
+                    None => "sort=recent_first".to_owned(),
+                };
+                json!({
+                    "request": {
+                        "method": "GET",
+                        "url": format!("https://listen-api.listennotes.com/api/v2/podcasts/dummy_id?{}", query),
+                        "headers": [],
+                        "body": null
+                    },
+                    "response": { "status": status, "headers": [], "body": body.to_string() }
+                })
+            })
+            .collect();
+        std::fs::write(&path, json!({ "interactions": interactions }).to_string()).unwrap();
+        let cassette = podcast_api::Cassette::replay(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        cassette
+    }
+
+    /// Podcast fixture split in two pages of 5 episodes.
+    fn podcast_pages() -> (serde_json::Value, serde_json::Value) {
+        let podcast: serde_json::Value = serde_json::from_str(include_str!("../fixtures/podcast.json")).unwrap();
+        let episodes = podcast["episodes"].as_array().unwrap();
+        let mut first = podcast.clone();
+        first["episodes"] = json!(episodes[..5]);
+        first["next_episode_pub_date"] = episodes[4]["pub_date_ms"].clone();
+        let mut second = podcast.clone();
+        second["episodes"] = json!(episodes[5..]);
+        second["next_episode_pub_date"] = episodes[9]["pub_date_ms"].clone();
+        second["earliest_pub_date_ms"] = episodes[9]["pub_date_ms"].clone();
+        (first, second)
+    }
+
+    #[test]
+    fn podcast_episodes_stream_pages() {
+        b!(async {
+            let (first, second) = podcast_pages();
+            let cursor = first["next_episode_pub_date"].as_u64();
+            let expected: Vec<_> = first["episodes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .chain(second["episodes"].as_array().unwrap())
+                .map(|episode| episode["id"].as_str().unwrap().to_owned())
+                .collect();
+            let client = podcast_api::Client::new(None)
+                .with_cassette(episode_pages("episode-pages", &[(None, 200, first), (cursor, 200, second)]));
+            let mut episodes =
+                client.podcast_episodes_stream("dummy_id", podcast_api::params::EpisodeSort::RecentFirst);
+            let mut ids = Vec::new();
+            while let Some(episode) = episodes.try_next().await.unwrap() {
+                ids.push(episode.id);
+            }
+            assert_eq!(ids, expected);
+            assert!(episodes.try_next().await.unwrap().is_none());
+        });
+    }
+
+    #[test]
+    fn podcast_episodes_stream_resume_after_error() {
+        b!(async {
+            let (first, second) = podcast_pages();
+            let cursor = first["next_episode_pub_date"].as_u64();
+            let client = podcast_api::Client::new(None).with_cassette(episode_pages(
+                "episode-resume",
+                &[
+                    (None, 200, first),
+                    (cursor, 503, json!({})),
+                    (cursor, 200, second.clone()),
+                ],
+            ));
+            let mut episodes =
+                client.podcast_episodes_stream("dummy_id", podcast_api::params::EpisodeSort::RecentFirst);
+            for _ in 0..5 {
+                episodes.try_next().await.unwrap().unwrap();
+            }
+            assert!(episodes.try_next().await.is_err());
+            assert!(episodes.try_next().await.unwrap().is_none());
+            assert_eq!(episodes.cursor().map(|cursor| cursor.pub_date_ms), cursor);
+
+            let resumed: Vec<_> = client
+                .podcast_episodes_stream("dummy_id", podcast_api::params::EpisodeSort::RecentFirst)
+                .resume_from(episodes.cursor().unwrap().clone())
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(resumed.len(), 5);
+            assert_eq!(resumed[0].id, second["episodes"][0]["id"].as_str().unwrap());
+        });
+    }
+
+    #[test]
+    fn podcast_episodes_stream_resume_inclusive_page() {
+        b!(async {
+            let (first, mut second) = podcast_pages();
+            let cursor = first["next_episode_pub_date"].as_u64();
+            // The API may include the episodes published at the cursor in the next page.
+            let mut episodes = first["episodes"].as_array().unwrap()[4..].to_vec();
+            episodes.extend(second["episodes"].as_array().unwrap().iter().cloned());
+            second["episodes"] = json!(episodes);
+            let client = podcast_api::Client::new(None).with_cassette(episode_pages(
+                "episode-inclusive",
+                &[(None, 200, first), (cursor, 200, second.clone())],
+            ));
+            let mut episodes =
+                client.podcast_episodes_stream("dummy_id", podcast_api::params::EpisodeSort::RecentFirst);
+            for _ in 0..5 {
+                episodes.try_next().await.unwrap().unwrap();
+            }
+            let cursor = episodes.cursor().unwrap().clone();
+            drop(episodes);
+
+            let resumed: Vec<_> = client
+                .podcast_episodes_stream("dummy_id", podcast_api::params::EpisodeSort::RecentFirst)
+                .resume_from(cursor.clone())
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(resumed.len(), 5);
+            assert!(resumed.iter().all(|episode| !cursor.ids.contains(&episode.id)));
+            assert_eq!(resumed[0].id, second["episodes"][1]["id"].as_str().unwrap());
+        });
+    }
+
     #[test]
     fn batch_fetch_podcasts() {
         b!(async {
@@ -223,6 +714,134 @@ mod mock {
         });
     }
 
+    #[test]
+    fn fetch_podcasts_by_ids() {
+        b!(async {
+            let server = MockServer::start();
+            let fixture: Vec<String> = server
+                .client()
+                .batch_fetch_podcasts(&json!({ "ids": "x" }))
+                .await
+                .unwrap()
+                .batch_podcasts()
+                .await
+                .unwrap()
+                .podcasts
+                .into_iter()
+                .map(|podcast| podcast.id)
+                .collect();
+            let mut ids: Vec<&str> = fixture.iter().rev().map(String::as_str).collect();
+            ids.push("missing");
+
+            let results = server.client().fetch_podcasts_by_ids(&ids, 2).await.unwrap();
+            let found: Vec<&str> = results.found.iter().map(|podcast| podcast.id.as_str()).collect();
+            assert_eq!(found, ids[..fixture.len()].to_vec());
+            assert_eq!(results.not_found, vec!["missing".to_owned()]);
+            // One call for the fixture ids, then 12 ids in two chunks.
+            assert_eq!(server.requests(), 3);
+        });
+    }
+
+    #[test]
+    fn playlist_tracks() {
+        b!(async {
+            let server = MockServer::start();
+            let client = server.client();
+            let mut playlist = client
+                .fetch_playlist_by_id("dummy", &json!({}))
+                .await
+                .unwrap()
+                .playlist()
+                .await
+                .unwrap();
+            let mut podcast = client
+                .batch_fetch_podcasts(&json!({ "ids": "x" }))
+                .await
+                .unwrap()
+                .batch_podcasts()
+                .await
+                .unwrap()
+                .podcasts
+                .remove(0);
+            let episodes = client
+                .batch_fetch_episodes(&json!({ "ids": "x" }))
+                .await
+                .unwrap()
+                .batch_episodes()
+                .await
+                .unwrap()
+                .episodes;
+            podcast.latest_episode_id = episodes[1].id.clone();
+            playlist.items[0].data = podcast_api::model::PlaylistItemData::Podcast(Box::new(podcast));
+
+            let tracks = client.playlist_tracks(&playlist, 2).await.unwrap();
+            // The fixture has a deleted episode, which is left out.
+            assert_eq!(tracks.len(), playlist.items.len() - 1);
+            assert_eq!(tracks[0].audio, episodes[1].audio);
+            assert_eq!(server.requests(), 4);
+
+            let m3u8 = podcast_api::playlist::m3u8(&playlist.name, &tracks);
+            assert_eq!(
+                m3u8.lines().filter(|line| line.starts_with("#EXTINF:")).count(),
+                tracks.len()
+            );
+            let xspf = podcast_api::playlist::xspf(&playlist.name, &tracks);
+            assert_eq!(xspf.matches("<track>").count(), tracks.len());
+        });
+    }
+
+    #[test]
+    fn import_export_opml() {
+        b!(async {
+            let server = MockServer::start();
+            let client = server.client();
+            let opml = r#"<opml version="2.0"><body>
+                <outline text="Exponent" xmlUrl="http://exponent.fm/feed"/>
+                <outline text="Unknown" xmlUrl="https://example.com/unknown.xml"/>
+                <outline text="Binge Mode" xmlUrl="https://podcasts.apple.com/us/podcast/binge-mode/id1243247464"/>
+            </body></opml>"#;
+
+            let import = client.import_opml(opml, 2).await.unwrap();
+            let matched: Vec<(&str, &str)> = import
+                .matched
+                .iter()
+                .map(|(feed, podcast)| (feed.title.as_deref().unwrap(), podcast.id.as_str()))
+                .collect();
+            assert_eq!(
+                matched,
+                vec![
+                    ("Exponent", "37589a3e121e40debe4cef3d9638932a"),
+                    ("Binge Mode", "9cf19c590ff0484d97b18b329fed0c6a")
+                ]
+            );
+            assert_eq!(import.unmatched.len(), 1);
+            assert_eq!(import.unmatched[0].xml_url, "https://example.com/unknown.xml");
+            // One batch call by RSS url, then a search for the unmatched feed.
+            assert_eq!(server.requests(), 2);
+
+            // Replaying the import with a failed search of the unknown feed leaves it unmatched.
+            let path = std::env::temp_dir().join(format!("podcast-api-opml-{}.json", std::process::id()));
+            let recording = client.clone().with_cassette(podcast_api::Cassette::record(&path));
+            recording.import_opml(opml, 2).await.unwrap();
+            recording.cassette().unwrap().save().unwrap();
+            let mut tape: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+            tape["interactions"][1]["response"]["status"] = json!(500);
+            std::fs::write(&path, tape.to_string()).unwrap();
+            let replay = podcast_api::Client::new(None).with_cassette(podcast_api::Cassette::replay(&path).unwrap());
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(replay.import_opml(opml, 2).await.unwrap(), import);
+
+            let ids: Vec<&str> = import.matched.iter().map(|(_, podcast)| podcast.id.as_str()).collect();
+            let exported = client.export_opml("Subscriptions", &ids, 2).await.unwrap();
+            let feeds = podcast_api::opml::parse(&exported).unwrap();
+            let rsses: Vec<&str> = feeds.iter().map(|feed| feed.xml_url.as_str()).collect();
+            assert_eq!(
+                rsses,
+                vec!["https://exponent.fm/feed/", "https://feeds.megaphone.fm/binge-mode"]
+            );
+        });
+    }
+
     #[test]
     fn fetch_episode_by_id() {
         b!(async {
@@ -239,6 +858,15 @@ mod mock {
         });
     }
 
+    #[test]
+    fn fetch_episode_by_id_typed() {
+        b!(async {
+            let response = client().fetch_episode_by_id("dummy_id", &json!({})).await.unwrap();
+            let episode = response.episode().await.unwrap();
+            assert!(!episode.podcast.unwrap().rss.is_empty());
+        });
+    }
+
     #[test]
     fn batch_fetch_episodes() {
         b!(async {
@@ -264,6 +892,19 @@ mod mock {
         });
     }
 
+    #[test]
+    fn fetch_episodes_by_ids() {
+        b!(async {
+            let results = client()
+                .fetch_episodes_by_ids(&["c577d55b2b2b483c969fae3ceb58e362", "missing"], 4)
+                .await
+                .unwrap();
+            assert_eq!(results.found.len(), 1);
+            assert_eq!(results.found[0].id, "c577d55b2b2b483c969fae3ceb58e362");
+            assert_eq!(results.not_found, vec!["missing".to_owned()]);
+        });
+    }
+
     #[test]
     fn fetch_curated_podcasts_list_by_id() {
         b!(async {
@@ -482,6 +1123,29 @@ mod mock {
         });
     }
 
+    #[test]
+    fn submit_podcast_encoded() {
+        b!(async {
+            let response = client()
+                .submit_podcast(&json!({
+                    "rss": "https://example.com/feed?id=1&format=rss",
+                    "email": "podcaster+rss@example.com"
+                }))
+                .await
+                .unwrap();
+            let p: Vec<(String, String)> = form_urlencoded::parse(response.request.body().unwrap().as_bytes().unwrap())
+                .into_owned()
+                .collect();
+            assert_eq!(
+                p,
+                vec![
+                    ("email".to_owned(), "podcaster+rss@example.com".to_owned()),
+                    ("rss".to_owned(), "https://example.com/feed?id=1&format=rss".to_owned()),
+                ]
+            );
+        });
+    }
+
     #[test]
     fn delete_podcast() {
         b!(async {
@@ -544,3 +1208,167 @@ mod mock {
         });
     }      
 }
+
+mod blocking {
+    use serde_json::json;
+
+    use podcast_api::{Error, MockServer, RetryPolicy};
+    use std::sync::OnceLock;
+
+    fn server() -> &'static MockServer {
+        static SERVER: OnceLock<MockServer> = OnceLock::new();
+        SERVER.get_or_init(MockServer::start)
+    }
+
+    fn client(server: &MockServer) -> podcast_api::blocking::Client {
+        podcast_api::blocking::Client::builder()
+            .base_url(server.url())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn search() {
+        let response = client(server())
+            .search(&json!({
+                "q": "dummy",
+                "sort_by_date": 1
+            }))
+            .unwrap();
+        assert_eq!(response.request.method(), http::Method::GET);
+        assert_eq!(response.request.url().path(), "/api/v2/search");
+        assert_eq!(response.attempts, 1);
+        assert!(response.usage().usage.is_some());
+        let results = response.search_results().unwrap();
+        assert!(!results.results.is_empty());
+    }
+
+    #[test]
+    fn builder() {
+        let server = MockServer::start();
+        let client: podcast_api::blocking::Client = podcast_api::blocking::Client::builder()
+            .base_url(server.url())
+            .header(
+                http::HeaderName::from_static("x-team"),
+                http::HeaderValue::from_static("podcasts"),
+            )
+            .user_agent("my-app")
+            .build()
+            .unwrap();
+        client.search(&json!({ "q": "dummy" })).unwrap();
+        let headers = server.last_request_headers().unwrap();
+        assert_eq!(headers["x-team"], "podcasts");
+        assert_eq!(headers[http::header::USER_AGENT], "my-app");
+    }
+
+    #[test]
+    fn batch_fetch_podcasts() {
+        let response = client(server())
+            .batch_fetch_podcasts(&json!({
+                "ids": "2968d8b6f0a74a85ab2e5a1b0ee4e2d0,cf9c5c6df3c7445e8ae8e4d1db8a6ddd"
+            }))
+            .unwrap();
+        assert_eq!(response.request.method(), http::Method::POST);
+        assert_eq!(response.request.url().path(), "/api/v2/podcasts");
+        assert!(!response.batch_podcasts().unwrap().podcasts.is_empty());
+    }
+
+    #[test]
+    fn fetch_podcast_by_id() {
+        let podcast = client(server())
+            .fetch_podcast_by_id("4d3fe717742d4963a85562e9f84d8c79", &json!({}))
+            .unwrap()
+            .podcast()
+            .unwrap();
+        assert!(!podcast.title.is_empty());
+    }
+
+    #[test]
+    fn delete_podcast() {
+        let response = client(server())
+            .delete_podcast("4d3fe717742d4963a85562e9f84d8c79", &json!({ "reason": "abc" }))
+            .unwrap();
+        assert_eq!(response.request.method(), http::Method::DELETE);
+        assert_eq!(response.json().unwrap()["status"], "in review");
+    }
+
+    #[test]
+    fn errors_and_retries() {
+        let server = MockServer::start();
+        let client = client(&server);
+        assert!(matches!(
+            client.search(&json!({ "unknown": 1 })),
+            Err(Error::InvalidRequestError(_))
+        ));
+
+        server.fail_next(http::StatusCode::SERVICE_UNAVAILABLE);
+        let client = client.with_retry_policy(RetryPolicy::new().base_delay(std::time::Duration::from_millis(1)));
+        let response = client.fetch_podcast_genres(&json!({})).unwrap();
+        assert_eq!(response.attempts, 2);
+        assert!(client.usage().usage.is_some());
+    }
+
+    #[test]
+    fn unsupported_configuration() {
+        assert!(matches!(
+            podcast_api::Client::builder()
+                .cache(podcast_api::ResponseCache::new())
+                .build_blocking(),
+            Err(Error::ValidationError(_))
+        ));
+    }
+}
+
+#[cfg(feature = "cli")]
+mod cli {
+    use podcast_api::MockServer;
+    use std::process::{Command, Output};
+
+    fn listennotes(server: &MockServer, args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_listennotes"))
+            .args(["--base-url", &server.url()])
+            .args(args)
+            .env_remove("LISTEN_API_KEY")
+            .env("XDG_CONFIG_HOME", std::env::temp_dir().join("podcast-api-no-config"))
+            .output()
+            .unwrap()
+    }
+
+    #[test]
+    fn search_json() {
+        let server = MockServer::start();
+        let output = listennotes(
+            &server,
+            &["search", "star wars", "--type", "podcast", "-p", "safe_mode=1"],
+        );
+        assert!(output.status.success());
+        let body: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert!(!body["results"].as_array().unwrap().is_empty());
+        assert_eq!(server.requests(), 1);
+    }
+
+    #[test]
+    fn genres_table() {
+        let server = MockServer::start();
+        let output = listennotes(&server, &["genres", "--top-level-only", "--output", "table"]);
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.starts_with("genres ("));
+    }
+
+    #[test]
+    fn exit_codes() {
+        let server = MockServer::start();
+        server.require_api_key("right_key");
+        let output = listennotes(&server, &["--api-key", "wrong_key", "episode", "abc"]);
+        assert_eq!(output.status.code(), Some(3));
+        assert!(String::from_utf8(output.stderr).unwrap().starts_with("Error: "));
+
+        server.fail_next(http::StatusCode::NOT_FOUND);
+        let output = listennotes(&server, &["--api-key", "right_key", "podcast", "abc"]);
+        assert_eq!(output.status.code(), Some(4));
+
+        let output = listennotes(&server, &["podcast"]);
+        assert_eq!(output.status.code(), Some(2));
+    }
+}
@@ -0,0 +1,54 @@
+//! Tests of the wasm client, run in Node with `wasm-pack test --node --features wasm`.
+//!
+//! Tests calling the mock API need network access, so they are ignored unless run with `--include-ignored`.
+#![cfg(target_arch = "wasm32")]
+
+use serde_json::json;
+use std::time::Duration;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn search_results() {
+    let results: podcast_api::model::SearchResults =
+        serde_json::from_str(include_str!("../fixtures/search.json")).unwrap();
+    assert!(!results.results.is_empty());
+}
+
+#[wasm_bindgen_test]
+#[ignore]
+async fn search() {
+    let client = podcast_api::Client::new(None);
+    let response = client.search(&json!({ "q": "dummy" })).await.unwrap();
+    assert_eq!(response.request.url().path(), "/api/v2/search");
+    assert_eq!(response.attempts, 1);
+    assert!(!response.search_results().await.unwrap().results.is_empty());
+}
+
+#[wasm_bindgen_test]
+#[ignore]
+async fn fetch_podcast_genres() {
+    let client = podcast_api::Client::new(None);
+    let genres = client
+        .fetch_podcast_genres(&json!({ "top_level_only": 1 }))
+        .await
+        .unwrap()
+        .genres()
+        .await
+        .unwrap();
+    assert!(!genres.genres.is_empty());
+}
+
+// Calls an unroutable address, whose connection attempts go unanswered, so only the timeout can end the call.
+#[wasm_bindgen_test]
+async fn timeout() {
+    let client = podcast_api::Client::builder()
+        .base_url("http://10.255.255.1/api/v2")
+        .timeout(Duration::from_millis(500))
+        .build()
+        .unwrap();
+    let started = js_sys::Date::now();
+    let response = client.search(&json!({ "q": "dummy" })).await;
+    let elapsed = js_sys::Date::now() - started;
+    assert!(matches!(response, Err(podcast_api::Error::ApiConnectionError { .. })));
+    assert!((500.0..5000.0).contains(&elapsed), "failed after {}ms", elapsed);
+}